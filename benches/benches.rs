@@ -7,8 +7,10 @@ use byteorder::{ReadBytesExt, LittleEndian, BigEndian, WriteBytesExt};
 use std::io::{Read, Write, Cursor};
 
 const COUNT_8:  usize = 2048;
+#[allow(dead_code)]
 const COUNT_16: usize = COUNT_8 / 2;
 const COUNT_32: usize = COUNT_8 / 4;
+#[allow(dead_code)]
 const COUNT_64: usize = COUNT_8 / 8;
 
 
@@ -0,0 +1,226 @@
+use crate::integer::{ Unsigned, Signed };
+use crate::io::invalid_data;
+use std::io::{ Read, Write, Result, Error, ErrorKind };
+
+
+/// Where the first nibble of each byte is stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NibbleOrder {
+
+    /// The first nibble is stored in the upper four bits of each byte, so `0x12` contains the digits `1, 2`.
+    /// Used by real time clock chips and COBOL packed decimals.
+    HighFirst,
+
+    /// The first nibble is stored in the lower four bits of each byte, so `0x21` contains the digits `1, 2`.
+    /// Used by some smart card records.
+    LowFirst,
+}
+
+/// Whether and where a packed decimal number contains a sign nibble.
+///
+/// When reading, the nibbles `B` and `D` mean negative, while `A`, `C`, `E` and `F` mean positive.
+/// When writing, `C` is used for positive and `D` for negative signed numbers,
+/// and `F` is used for unsigned numbers, following the COBOL conventions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SignNibble {
+
+    /// The number only consists of digits, and cannot be negative.
+    None,
+
+    /// The sign nibble comes before the first digit.
+    Leading,
+
+    /// The sign nibble comes after the last digit, as in COBOL `COMP-3` fields.
+    Trailing,
+}
+
+/// Describes how the digits of a packed decimal number are stored.
+///
+/// The digits are stored with the most significant digit first.
+/// If the digits and the sign nibble do not fill a whole number of bytes,
+/// an additional zero digit is inserted before the first digit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BcdLayout {
+
+    /// Where the first nibble of each byte is stored.
+    pub nibble_order: NibbleOrder,
+
+    /// Whether and where the number contains a sign nibble.
+    pub sign: SignNibble,
+}
+
+impl BcdLayout {
+
+    /// Two digits per byte, upper nibble first, without a sign. Used by real time clock chips.
+    pub const UNSIGNED: Self = BcdLayout { nibble_order: NibbleOrder::HighFirst, sign: SignNibble::None };
+
+    /// Two digits per byte, upper nibble first, followed by a sign nibble. Used by COBOL `COMP-3` fields.
+    pub const COMP3: Self = BcdLayout { nibble_order: NibbleOrder::HighFirst, sign: SignNibble::Trailing };
+
+    /// The number of bytes that a number with the specified number of digits takes up in this layout.
+    #[inline]
+    pub fn byte_len(self, digits: usize) -> usize {
+        self.nibble_len(digits).div_ceil(2)
+    }
+
+    #[inline]
+    fn nibble_len(self, digits: usize) -> usize {
+        match self.sign {
+            SignNibble::None => digits,
+            SignNibble::Leading | SignNibble::Trailing => digits + 1,
+        }
+    }
+}
+
+impl Default for BcdLayout {
+    #[inline]
+    fn default() -> Self {
+        BcdLayout::UNSIGNED
+    }
+}
+
+
+/// A `std::io::Read` input stream which supports reading packed binary coded decimal numbers,
+/// which store one decimal digit in each nibble.
+///
+/// Returns an `InvalidData` error if a nibble is not a valid digit or sign,
+/// if the padding digit is not zero, or if the number does not fit into the requested type.
+///
+/// This extension trait is implemented for all `Read` types.
+pub trait ReadBcd: Read {
+
+    /// Read an unsigned number with the specified number of digits, stored in the layout `BcdLayout::UNSIGNED`.
+    fn read_bcd<T: Unsigned>(&mut self, digits: usize) -> Result<T> {
+        self.read_bcd_with_layout(digits, BcdLayout::UNSIGNED)
+    }
+
+    /// Read an unsigned number with the specified number of digits.
+    /// Returns an `InvalidData` error if the sign nibble is negative.
+    fn read_bcd_with_layout<T: Unsigned>(&mut self, digits: usize, layout: BcdLayout) -> Result<T> {
+        let (negative, magnitude) = read_decimal(self, digits, layout, std::any::type_name::<T>())?;
+        if negative && magnitude != 0 { return Err(invalid_data("negative BCD number cannot be read as an unsigned integer")); }
+
+        let value = T::from_u128_truncated(magnitude);
+        if value.to_u128() != magnitude { return Err(does_not_fit()); }
+        Ok(value)
+    }
+
+    /// Read a signed number with the specified number of digits.
+    fn read_signed_bcd<T: Signed>(&mut self, digits: usize, layout: BcdLayout) -> Result<T> {
+        let (negative, magnitude) = read_decimal(self, digits, layout, std::any::type_name::<T>())?;
+        if magnitude > 1 << 127 { return Err(does_not_fit()); }
+
+        let signed = if negative { (magnitude as i128).wrapping_neg() } else { magnitude as i128 };
+        if !negative && signed < 0 { return Err(does_not_fit()); }
+
+        let value = T::from_i128_truncated(signed);
+        if value.to_i128() != signed { return Err(does_not_fit()); }
+        Ok(value)
+    }
+}
+
+/// A `std::io::Write` output stream which supports writing packed binary coded decimal numbers,
+/// which store one decimal digit in each nibble.
+///
+/// Numbers with fewer digits are padded with leading zeroes.
+/// Returns an `InvalidInput` error if the number has more digits than requested.
+///
+/// This extension trait is implemented for all `Write` types.
+pub trait WriteBcd: Write {
+
+    /// Write an unsigned number with the specified number of digits, in the layout `BcdLayout::UNSIGNED`.
+    fn write_bcd<T: Unsigned>(&mut self, value: T, digits: usize) -> Result<()> {
+        self.write_bcd_with_layout(value, digits, BcdLayout::UNSIGNED)
+    }
+
+    /// Write an unsigned number with the specified number of digits.
+    /// If the layout has a sign nibble, the unsigned sign `F` is written.
+    fn write_bcd_with_layout<T: Unsigned>(&mut self, value: T, digits: usize, layout: BcdLayout) -> Result<()> {
+        write_decimal(self, value.to_u128(), 0xF, digits, layout)
+    }
+
+    /// Write a signed number with the specified number of digits.
+    /// If the layout has a sign nibble, `C` is written for positive numbers and `D` for negative numbers.
+    /// Returns an `InvalidInput` error if the number is negative and the layout has no sign nibble.
+    fn write_signed_bcd<T: Signed>(&mut self, value: T, digits: usize, layout: BcdLayout) -> Result<()> {
+        let value = value.to_i128();
+
+        if value < 0 && layout.sign == SignNibble::None {
+            return Err(Error::new(ErrorKind::InvalidInput, "negative number requires a BCD layout with a sign nibble"));
+        }
+
+        let sign = if value < 0 { 0xD } else { 0xC };
+        write_decimal(self, value.unsigned_abs(), sign, digits, layout)
+    }
+}
+
+impl<R: Read + ?Sized> ReadBcd for R {}
+impl<W: Write + ?Sized> WriteBcd for W {}
+
+
+fn does_not_fit() -> Error {
+    invalid_data("BCD number does not fit into the target type")
+}
+
+// returns whether the sign is negative, and the magnitude of the number
+fn read_decimal<R: Read + ?Sized>(read: &mut R, digits: usize, layout: BcdLayout, type_name: &'static str) -> Result<(bool, u128)> {
+    let mut bytes = vec![0_u8; layout.byte_len(digits)];
+    crate::error::read_exact(read, &mut bytes, type_name)?;
+
+    let mut nibbles = bytes.iter().flat_map(|&byte| match layout.nibble_order {
+        NibbleOrder::HighFirst => [byte >> 4, byte & 0xF],
+        NibbleOrder::LowFirst => [byte & 0xF, byte >> 4],
+    });
+
+    let parse_sign = |nibble: Option<u8>| match nibble {
+        Some(0xB) | Some(0xD) => Ok(true),
+        Some(0xA) | Some(0xC) | Some(0xE) | Some(0xF) => Ok(false),
+        _ => Err(invalid_data("invalid BCD sign nibble")),
+    };
+
+    let mut negative = false;
+    if layout.sign == SignNibble::Leading { negative = parse_sign(nibbles.next())?; }
+
+    if layout.nibble_len(digits) % 2 == 1 && nibbles.next() != Some(0) {
+        return Err(invalid_data("BCD padding digit is not zero"));
+    }
+
+    let mut magnitude = 0_u128;
+    for digit in nibbles.by_ref().take(digits) {
+        if digit > 9 { return Err(invalid_data("invalid BCD digit")); }
+
+        magnitude = magnitude.checked_mul(10)
+            .and_then(|magnitude| magnitude.checked_add(u128::from(digit)))
+            .ok_or_else(does_not_fit)?;
+    }
+
+    if layout.sign == SignNibble::Trailing { negative = parse_sign(nibbles.next())?; }
+    Ok((negative, magnitude))
+}
+
+fn write_decimal<W: Write + ?Sized>(write: &mut W, mut magnitude: u128, sign: u8, digits: usize, layout: BcdLayout) -> Result<()> {
+    let mut nibbles = vec![0_u8; 2 * layout.byte_len(digits)];
+
+    let digit_nibbles = match layout.sign {
+        SignNibble::None => &mut nibbles[..],
+        SignNibble::Leading => { nibbles[0] = sign; &mut nibbles[1 ..] },
+        SignNibble::Trailing => { let last = nibbles.len() - 1; nibbles[last] = sign; &mut nibbles[.. last] },
+    };
+
+    // fill the digits starting with the least significant digit, leaving the padding digit at zero
+    for nibble in digit_nibbles.iter_mut().rev().take(digits) {
+        *nibble = (magnitude % 10) as u8;
+        magnitude /= 10;
+    }
+
+    if magnitude != 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "number has more digits than the BCD field"));
+    }
+
+    let bytes: Vec<u8> = nibbles.chunks_exact(2).map(|pair| match layout.nibble_order {
+        NibbleOrder::HighFirst => (pair[0] << 4) | pair[1],
+        NibbleOrder::LowFirst => (pair[1] << 4) | pair[0],
+    }).collect();
+
+    write.write_all(&bytes)
+}
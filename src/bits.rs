@@ -0,0 +1,252 @@
+use crate::integer::{ Unsigned, Signed };
+use std::io::{ Read, Write, Result, Error, ErrorKind };
+
+
+/// The order in which the bits of each byte are consumed or produced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BitOrder {
+
+    /// Start with the most significant bit of each byte.
+    /// The first bit of a bitfield ends up as its most significant bit.
+    /// Used by H.264, MPEG and most network protocols.
+    MostSignificantFirst,
+
+    /// Start with the least significant bit of each byte.
+    /// The first bit of a bitfield ends up as its least significant bit.
+    /// Used by deflate.
+    LeastSignificantFirst,
+}
+
+/// Reads bitfields of any width from a `std::io::Read` stream.
+///
+/// Never pulls more bytes from the inner reader than necessary,
+/// so after aligning to the next byte, the inner reader can be used directly,
+/// for example to read values with `ReadEndian`.
+/// As bytes are read one by one, consider wrapping unbuffered streams in a `std::io::BufReader`.
+#[derive(Debug)]
+pub struct BitReader<R> {
+    inner: R,
+    order: BitOrder,
+    current: u8,
+    remaining_bits: u32,
+}
+
+/// Writes bitfields of any width to a `std::io::Write` stream.
+///
+/// Incomplete bytes are only written when aligning to the next byte,
+/// so call `finish` or `byte_aligned` before dropping the writer.
+#[derive(Debug)]
+pub struct BitWriter<W> {
+    inner: W,
+    order: BitOrder,
+    current: u8,
+    filled_bits: u32,
+}
+
+impl<R: Read> BitReader<R> {
+
+    /// Read bits from the inner reader, starting at the next byte of the reader.
+    pub fn new(inner: R, order: BitOrder) -> Self {
+        BitReader { inner, order, current: 0, remaining_bits: 0 }
+    }
+
+    /// Read an unsigned integer consisting of `count` bits.
+    /// Returns an `InvalidInput` error if `count` exceeds the number of bits in `T`.
+    pub fn read_bits<T: Unsigned>(&mut self, count: u32) -> Result<T> {
+        if count > T::BITS { return Err(too_many_bits()); }
+
+        let mut result = 0_u128;
+        let mut filled = 0;
+
+        while filled < count {
+            if self.remaining_bits == 0 {
+                let mut byte = [0_u8];
+                self.inner.read_exact(&mut byte)?;
+                self.current = byte[0];
+                self.remaining_bits = 8;
+            }
+
+            let take = self.remaining_bits.min(count - filled);
+            let mask = (1_u16 << take) - 1;
+
+            match self.order {
+                BitOrder::MostSignificantFirst => {
+                    let bits = (u16::from(self.current) >> (self.remaining_bits - take)) & mask;
+                    result = (result << take) | u128::from(bits);
+                },
+
+                BitOrder::LeastSignificantFirst => {
+                    let bits = (u16::from(self.current) >> (8 - self.remaining_bits)) & mask;
+                    result |= u128::from(bits) << filled;
+                },
+            }
+
+            self.remaining_bits -= take;
+            filled += take;
+        }
+
+        Ok(T::from_u128_truncated(result))
+    }
+
+    /// Read a two's complement integer consisting of `count` bits, and sign-extend it.
+    /// Returns an `InvalidInput` error if `count` exceeds the number of bits in `T`.
+    pub fn read_signed_bits<T: Signed>(&mut self, count: u32) -> Result<T> {
+        let bits = self.read_bits::<T::Unsigned>(count)?.to_u128();
+        if count == 0 { return Ok(T::default()); }
+
+        let unused = 128 - count;
+        Ok(T::from_i128_truncated(((bits << unused) as i128) >> unused))
+    }
+
+    /// Read a single bit.
+    pub fn read_bit(&mut self) -> Result<bool> {
+        Ok(self.read_bits::<u8>(1)? == 1)
+    }
+
+    /// Whether the next bit is the first bit of a byte.
+    pub fn is_byte_aligned(&self) -> bool {
+        self.remaining_bits == 0
+    }
+
+    /// Discard the remaining bits of the current byte, if any.
+    pub fn align_to_byte(&mut self) {
+        self.remaining_bits = 0;
+    }
+
+    /// Discard the remaining bits of the current byte,
+    /// and access the inner reader, for example to read values with `ReadEndian`.
+    /// Afterwards, the bit reader continues at the next byte of the inner reader.
+    pub fn byte_aligned(&mut self) -> &mut R {
+        self.align_to_byte();
+        &mut self.inner
+    }
+
+    /// The bit order of this reader.
+    pub fn bit_order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Discard the remaining bits of the current byte, and return the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<W: Write> BitWriter<W> {
+
+    /// Write bits to the inner writer, starting at the next byte of the writer.
+    pub fn new(inner: W, order: BitOrder) -> Self {
+        BitWriter { inner, order, current: 0, filled_bits: 0 }
+    }
+
+    /// Write the lowest `count` bits of an unsigned integer.
+    /// Returns an `InvalidInput` error if `count` exceeds the number of bits in `T`,
+    /// or if the value does not fit into `count` bits.
+    pub fn write_bits<T: Unsigned>(&mut self, value: T, count: u32) -> Result<()> {
+        if count > T::BITS { return Err(too_many_bits()); }
+
+        let value = value.to_u128();
+        if count < 128 && value >> count != 0 { return Err(value_too_large()); }
+
+        self.write_u128_bits(value, count)
+    }
+
+    /// Write the lowest `count` bits of a two's complement integer.
+    /// Returns an `InvalidInput` error if `count` exceeds the number of bits in `T`,
+    /// or if the value does not fit into `count` bits.
+    pub fn write_signed_bits<T: Signed>(&mut self, value: T, count: u32) -> Result<()> {
+        if count > T::BITS { return Err(too_many_bits()); }
+
+        let value = value.to_i128();
+        if count == 0 && value != 0 { return Err(value_too_large()); }
+
+        // the value fits if all bits above the sign bit repeat the sign bit
+        if count != 0 && count < 128 && (value >> (count - 1)) != 0 && (value >> (count - 1)) != -1 {
+            return Err(value_too_large());
+        }
+
+        let mask = if count == 128 { u128::MAX } else { (1_u128 << count) - 1 };
+        self.write_u128_bits(value as u128 & mask, count)
+    }
+
+    /// Write a single bit.
+    pub fn write_bit(&mut self, bit: bool) -> Result<()> {
+        self.write_u128_bits(u128::from(bit), 1)
+    }
+
+    fn write_u128_bits(&mut self, value: u128, count: u32) -> Result<()> {
+        let mut written = 0;
+
+        while written < count {
+            let take = (8 - self.filled_bits).min(count - written);
+            let mask = (1_u128 << take) - 1;
+
+            match self.order {
+                BitOrder::MostSignificantFirst => {
+                    let bits = (value >> (count - written - take)) & mask;
+                    self.current |= (bits as u8) << (8 - self.filled_bits - take);
+                },
+
+                BitOrder::LeastSignificantFirst => {
+                    let bits = (value >> written) & mask;
+                    self.current |= (bits as u8) << self.filled_bits;
+                },
+            }
+
+            self.filled_bits += take;
+            written += take;
+
+            if self.filled_bits == 8 {
+                self.inner.write_all(&[self.current])?;
+                self.current = 0;
+                self.filled_bits = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the next bit is the first bit of a byte.
+    pub fn is_byte_aligned(&self) -> bool {
+        self.filled_bits == 0
+    }
+
+    /// Fill the remaining bits of the current byte with zeroes and write it, if any.
+    pub fn align_to_byte(&mut self) -> Result<()> {
+        if self.filled_bits != 0 {
+            self.inner.write_all(&[self.current])?;
+            self.current = 0;
+            self.filled_bits = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Fill the remaining bits of the current byte with zeroes, and access the inner writer,
+    /// for example to write values with `WriteEndian`.
+    /// Afterwards, the bit writer continues at the next byte of the inner writer.
+    pub fn byte_aligned(&mut self) -> Result<&mut W> {
+        self.align_to_byte()?;
+        Ok(&mut self.inner)
+    }
+
+    /// The bit order of this writer.
+    pub fn bit_order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Fill the remaining bits of the current byte with zeroes, and return the inner writer.
+    pub fn finish(mut self) -> Result<W> {
+        self.align_to_byte()?;
+        Ok(self.inner)
+    }
+}
+
+
+fn too_many_bits() -> Error {
+    Error::new(ErrorKind::InvalidInput, "bit count exceeds the number of bits in the type")
+}
+
+fn value_too_large() -> Error {
+    Error::new(ErrorKind::InvalidInput, "value does not fit into the bit count")
+}
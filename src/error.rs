@@ -0,0 +1,184 @@
+use std::fmt;
+use std::io::{ Read, ErrorKind };
+
+
+/// An error with additional context about a failed read:
+/// the name of the type that was read, how many bytes were requested,
+/// how many bytes were actually available, if known, and the offset in the stream, if known.
+///
+/// Reads through `ReadEndian` that run out of bytes attach this context to the returned `std::io::Error`,
+/// as do the reading traits for strings, packed integers, BCD numbers and IBM floats.
+/// These reads use `std::io::Read::read_exact`, which does not report how many bytes were available,
+/// so only the partial reads of `ReadPartial` and the iterators of `IterEndian` know the number of available bytes.
+///
+/// Convert the `std::io::Error` into this type to inspect it,
+/// for example by using `?` in a function that returns `Result<_, lebe::Error>`.
+/// Converting it back into a `std::io::Error` keeps the context,
+/// so existing `?` chains that use `std::io::Error` keep working.
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use lebe::prelude::*;
+///
+/// let mut reader = Cursor::new([0_u8, 1, 2, 3, 4, 5]);
+/// let _header: u32 = reader.read_from_big_endian()?;
+///
+/// let start = reader.position();
+/// let error = u32::read_from_big_endian(&mut reader).map_err(|error| lebe::Error::from(error).with_offset(start)).unwrap_err();
+///
+/// assert_eq!(error.type_name(), Some("u32"));
+/// assert_eq!((error.requested_bytes(), error.offset()), (Some(4), Some(4)));
+/// # Ok::<(), lebe::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    type_name: Option<&'static str>,
+    requested_bytes: Option<usize>,
+    available_bytes: Option<usize>,
+    offset: Option<u64>,
+    source: Option<std::io::Error>,
+}
+
+impl Error {
+
+    /// An `UnexpectedEof` error for a value of the named type,
+    /// where fewer bytes were available than requested.
+    pub fn unexpected_end(type_name: &'static str, requested_bytes: usize, available_bytes: usize) -> Self {
+        Error {
+            kind: ErrorKind::UnexpectedEof,
+            type_name: Some(type_name),
+            requested_bytes: Some(requested_bytes),
+            available_bytes: Some(available_bytes),
+            offset: None,
+            source: None,
+        }
+    }
+
+    /// Record the offset in the stream where the failed read started.
+    pub fn with_offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// The kind of the underlying `std::io::Error`.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The name of the type that was read, as returned by `std::any::type_name`.
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.type_name
+    }
+
+    /// The number of bytes the read required.
+    pub fn requested_bytes(&self) -> Option<usize> {
+        self.requested_bytes
+    }
+
+    /// The number of bytes that were available before the stream ended, if known.
+    /// These bytes have been consumed from the stream.
+    pub fn available_bytes(&self) -> Option<usize> {
+        self.available_bytes
+    }
+
+    /// The number of bytes that are missing to complete the read.
+    pub fn missing_bytes(&self) -> Option<usize> {
+        Some(self.requested_bytes? - self.available_bytes?)
+    }
+
+    /// The offset in the stream where the failed read started, if known.
+    pub fn offset(&self) -> Option<u64> {
+        self.offset
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.source, self.type_name) {
+            (Some(source), None) => write!(formatter, "{}", source)?,
+            (_, Some(type_name)) => write!(formatter, "failed to read `{}`", type_name)?,
+            (None, None) => write!(formatter, "{}", std::io::Error::from(self.kind))?,
+        }
+
+        match (self.requested_bytes, self.available_bytes) {
+            (Some(requested), Some(available)) => write!(formatter, ": requested {} bytes, but only {} were available", requested, available)?,
+            (Some(requested), None) => write!(formatter, ": requested {} bytes, but the stream ended", requested)?,
+            _ => {},
+        }
+
+        if let Some(offset) = self.offset {
+            write!(formatter, " at offset {}", offset)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_ref().map(|source| source as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl From<std::io::Error> for Error {
+
+    /// Recovers the context if the `std::io::Error` was created from an `Error`,
+    /// otherwise wraps the `std::io::Error` without any context.
+    fn from(error: std::io::Error) -> Self {
+        if error.get_ref().is_some_and(|inner| inner.is::<Error>()) {
+            let inner = error.into_inner().expect("inner error was checked");
+            return *inner.downcast::<Error>().expect("inner error type was checked");
+        }
+
+        Error {
+            kind: error.kind(),
+            type_name: None, requested_bytes: None,
+            available_bytes: None, offset: None,
+            source: Some(error),
+        }
+    }
+}
+
+impl From<Error> for std::io::Error {
+
+    /// Keeps the context inside the `std::io::Error`, so that it can be recovered later.
+    /// Returns the original `std::io::Error` if there is no context.
+    fn from(error: Error) -> Self {
+        let has_context = error.type_name.is_some() || error.offset.is_some()
+            || error.requested_bytes.is_some() || error.available_bytes.is_some();
+
+        match error.source {
+            Some(source) if !has_context => source,
+            _ => std::io::Error::new(error.kind, error),
+        }
+    }
+}
+
+
+// like `Read::read_exact`, but adds the type name and the number of requested bytes if the stream ends early
+#[inline]
+pub(crate) fn read_exact<R: Read + ?Sized>(read: &mut R, buffer: &mut [u8], type_name: &'static str) -> std::io::Result<()> {
+    let requested_bytes = buffer.len();
+
+    read.read_exact(buffer).map_err(|error| {
+        if error.kind() != ErrorKind::UnexpectedEof { return error; }
+
+        Error {
+            kind: ErrorKind::UnexpectedEof,
+            type_name: Some(type_name), requested_bytes: Some(requested_bytes),
+            available_bytes: None, offset: None,
+            source: Some(error),
+        }.into()
+    })
+}
+
+// replace the type name of an error that was returned while reading the underlying representation of a type,
+// for example the `u32` of a `char`
+pub(crate) fn for_type<T: ?Sized>(mut error: std::io::Error) -> std::io::Error {
+    if let Some(context) = error.get_mut().and_then(|inner| inner.downcast_mut::<Error>()) {
+        if context.type_name.is_some() { context.type_name = Some(std::any::type_name::<T>()); }
+    }
+
+    error
+}
@@ -0,0 +1,287 @@
+use crate::io::{ WriteEndian, ReadEndian, CHUNK_SIZE };
+use std::convert::TryInto;
+use std::io::{ Read, Write, Result };
+
+
+/// An 80-bit x87 extended precision float, as used for the sample rate in AIFF files and in x87 register dumps.
+/// Takes up ten bytes when written: a sign bit, a 15-bit exponent biased by 16383,
+/// and a 64-bit mantissa with an explicit integer bit.
+///
+/// In little endian, the mantissa comes first, followed by the sign and exponent.
+/// In big endian, the sign and exponent come first, followed by the mantissa.
+///
+/// This type only stores the bits, so it has no arithmetic, and comparing two values compares their bits.
+/// Convert it to `f64` to do calculations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct F80 {
+    sign_exponent: u16,
+    mantissa: u64,
+}
+
+const EXPONENT_BIAS: i32 = 16383;
+const MAX_EXPONENT: u16 = 0x7FFF;
+const INTEGER_BIT: u64 = 1 << 63;
+
+impl F80 {
+
+    /// Positive zero.
+    pub const ZERO: Self = F80 { sign_exponent: 0, mantissa: 0 };
+
+    /// Positive infinity.
+    pub const INFINITY: Self = F80 { sign_exponent: MAX_EXPONENT, mantissa: INTEGER_BIT };
+
+    /// Negative infinity.
+    pub const NEG_INFINITY: Self = F80 { sign_exponent: 0x8000 | MAX_EXPONENT, mantissa: INTEGER_BIT };
+
+    /// A quiet nan.
+    pub const NAN: Self = F80 { sign_exponent: MAX_EXPONENT, mantissa: INTEGER_BIT | (1 << 62) };
+
+    /// Create a value from the 16 bits containing the sign and the exponent, and the 64 bits of the mantissa.
+    #[inline]
+    pub const fn from_bits(sign_exponent: u16, mantissa: u64) -> Self {
+        F80 { sign_exponent, mantissa }
+    }
+
+    /// The 16 bits containing the sign bit and the biased exponent.
+    #[inline]
+    pub const fn sign_exponent_bits(self) -> u16 {
+        self.sign_exponent
+    }
+
+    /// The 64 bits of the mantissa, including the explicit integer bit.
+    #[inline]
+    pub const fn mantissa_bits(self) -> u64 {
+        self.mantissa
+    }
+
+    /// Create a value from its little endian byte representation.
+    #[inline]
+    pub fn from_le_bytes(bytes: [u8; 10]) -> Self {
+        let (mantissa, sign_exponent) = bytes.split_at(8);
+
+        F80 {
+            mantissa: u64::from_le_bytes(mantissa.try_into().unwrap()),
+            sign_exponent: u16::from_le_bytes(sign_exponent.try_into().unwrap()),
+        }
+    }
+
+    /// Create a value from its big endian byte representation.
+    #[inline]
+    pub fn from_be_bytes(bytes: [u8; 10]) -> Self {
+        let (sign_exponent, mantissa) = bytes.split_at(2);
+
+        F80 {
+            sign_exponent: u16::from_be_bytes(sign_exponent.try_into().unwrap()),
+            mantissa: u64::from_be_bytes(mantissa.try_into().unwrap()),
+        }
+    }
+
+    /// The little endian byte representation of this value.
+    #[inline]
+    pub fn to_le_bytes(self) -> [u8; 10] {
+        let mut bytes = [0_u8; 10];
+        bytes[.. 8].copy_from_slice(&self.mantissa.to_le_bytes());
+        bytes[8 ..].copy_from_slice(&self.sign_exponent.to_le_bytes());
+        bytes
+    }
+
+    /// The big endian byte representation of this value.
+    #[inline]
+    pub fn to_be_bytes(self) -> [u8; 10] {
+        let mut bytes = [0_u8; 10];
+        bytes[.. 2].copy_from_slice(&self.sign_exponent.to_be_bytes());
+        bytes[2 ..].copy_from_slice(&self.mantissa.to_be_bytes());
+        bytes
+    }
+
+    /// Whether the sign bit is set, including for negative zero and nan.
+    #[inline]
+    pub const fn is_sign_negative(self) -> bool {
+        self.sign_exponent & 0x8000 != 0
+    }
+
+    /// Whether this value is a nan.
+    #[inline]
+    pub const fn is_nan(self) -> bool {
+        self.sign_exponent & MAX_EXPONENT == MAX_EXPONENT && self.mantissa & !INTEGER_BIT != 0
+    }
+
+    /// Convert to the nearest `f64`, with ties to even.
+    ///
+    /// Values that are too large become infinite, and values that are too small become subnormal or zero.
+    /// Nan keeps its sign and as much of its payload as fits, and always stays a nan.
+    /// Denormals, and the unnormals that x87 processors reject, are converted by their numeric value.
+    pub fn to_f64(self) -> f64 {
+        let sign = u64::from(self.sign_exponent >> 15) << 63;
+        let exponent = self.sign_exponent & MAX_EXPONENT;
+
+        if exponent == MAX_EXPONENT {
+            let fraction = self.mantissa & !INTEGER_BIT;
+            if fraction == 0 { return f64::from_bits(sign | f64::INFINITY.to_bits()); }
+
+            // keep the upper payload bits, but make sure the result does not become infinity
+            let payload = (fraction >> 11).max(1);
+            return f64::from_bits(sign | f64::INFINITY.to_bits() | payload);
+        }
+
+        if self.mantissa == 0 { return f64::from_bits(sign); }
+
+        // the absolute value is `mantissa * 2^shift`, and lies below `2^top`
+        let shift = i32::from(exponent.max(1)) - EXPONENT_BIAS - 63;
+        let top = shift + (64 - self.mantissa.leading_zeros() as i32);
+
+        if top > 1024 { return f64::from_bits(sign | f64::INFINITY.to_bits()); }
+
+        // the exponent of the least significant bit of the result, which is fixed for subnormal results
+        let is_normal = top > -1022;
+        let result_shift = if is_normal { top - 53 } else { -1074 };
+        let mantissa = round_shift_right(self.mantissa, result_shift - shift);
+
+        // the integer bit of a normal mantissa adds one to the exponent field,
+        // which also handles a mantissa that was rounded up to the next power of two
+        let bits = if is_normal { (((top + 1021) as u64) << 52) + mantissa } else { mantissa };
+        f64::from_bits(sign | bits)
+    }
+}
+
+// shift right by `count` bits, rounding to nearest with ties to even, or shift left if `count` is negative
+#[inline]
+fn round_shift_right(value: u64, count: i32) -> u64 {
+    if count <= 0 { return value << -count; }
+    if count > 64 { return 0; }
+
+    let value = u128::from(value);
+    let truncated = value >> count;
+    let remainder = value & ((1 << count) - 1);
+    let half = 1 << (count - 1);
+
+    let rounded = if remainder > half || (remainder == half && truncated & 1 == 1) { truncated + 1 } else { truncated };
+    rounded as u64
+}
+
+impl From<f64> for F80 {
+
+    /// Exact, as every `f64` can be represented as an `F80`.
+    /// Nan keeps its sign and payload.
+    fn from(value: f64) -> Self {
+        let bits = value.to_bits();
+        let sign = ((bits >> 63) as u16) << 15;
+        let exponent = ((bits >> 52) & 0x7FF) as u16;
+        let fraction = bits & 0x000F_FFFF_FFFF_FFFF;
+
+        match exponent {
+            0x7FF => F80 { sign_exponent: sign | MAX_EXPONENT, mantissa: INTEGER_BIT | (fraction << 11) },
+            0 if fraction == 0 => F80 { sign_exponent: sign, mantissa: 0 },
+
+            // normalize subnormal values, as the larger exponent range can represent them as normal values
+            0 => {
+                let leading_zeros = fraction.leading_zeros() as u16;
+                F80 { sign_exponent: sign | (15372 - leading_zeros), mantissa: fraction << leading_zeros }
+            },
+
+            _ => F80 { sign_exponent: sign | (exponent + 15360), mantissa: INTEGER_BIT | (fraction << 11) },
+        }
+    }
+}
+
+impl From<f32> for F80 {
+
+    /// Exact, as every `f32` can be represented as an `F80`.
+    #[inline]
+    fn from(value: f32) -> Self {
+        F80::from(f64::from(value))
+    }
+}
+
+impl From<F80> for f64 {
+
+    /// Rounds to the nearest `f64`, see `F80::to_f64`.
+    #[inline]
+    fn from(value: F80) -> Self {
+        value.to_f64()
+    }
+}
+
+
+impl<W: Write> WriteEndian<F80> for W {
+    #[inline]
+    fn write_as_little_endian(&mut self, value: &F80) -> Result<()> {
+        self.write_all(&value.to_le_bytes())
+    }
+
+    #[inline]
+    fn write_as_big_endian(&mut self, value: &F80) -> Result<()> {
+        self.write_all(&value.to_be_bytes())
+    }
+}
+
+impl<R: Read> ReadEndian<F80> for R {
+    #[inline]
+    fn read_from_little_endian_into(&mut self, value: &mut F80) -> Result<()> {
+        let mut bytes = [0_u8; 10];
+        crate::error::read_exact(self, &mut bytes, std::any::type_name::<F80>())?;
+        *value = F80::from_le_bytes(bytes);
+        Ok(())
+    }
+
+    #[inline]
+    fn read_from_big_endian_into(&mut self, value: &mut F80) -> Result<()> {
+        let mut bytes = [0_u8; 10];
+        crate::error::read_exact(self, &mut bytes, std::any::type_name::<F80>())?;
+        *value = F80::from_be_bytes(bytes);
+        Ok(())
+    }
+}
+
+impl<W: Write> WriteEndian<[F80]> for W {
+    fn write_as_little_endian(&mut self, value: &[F80]) -> Result<()> {
+        write_chunks(self, value, F80::to_le_bytes)
+    }
+
+    fn write_as_big_endian(&mut self, value: &[F80]) -> Result<()> {
+        write_chunks(self, value, F80::to_be_bytes)
+    }
+}
+
+impl<R: Read> ReadEndian<[F80]> for R {
+    fn read_from_little_endian_into(&mut self, value: &mut [F80]) -> Result<()> {
+        read_chunks(self, value, F80::from_le_bytes)
+    }
+
+    fn read_from_big_endian_into(&mut self, value: &mut [F80]) -> Result<()> {
+        read_chunks(self, value, F80::from_be_bytes)
+    }
+}
+
+// read whole chunks of bytes at once, then decode each value
+fn read_chunks<R: Read>(read: &mut R, values: &mut [F80], decode: impl Fn([u8; 10]) -> F80) -> Result<()> {
+    let mut buffer = [0_u8; 10 * CHUNK_SIZE];
+
+    for chunk in values.chunks_mut(CHUNK_SIZE) {
+        let bytes = &mut buffer[.. 10 * chunk.len()];
+        crate::error::read_exact(read, bytes, std::any::type_name::<[F80]>())?;
+
+        for (value, bytes) in chunk.iter_mut().zip(bytes.chunks_exact(10)) {
+            *value = decode(bytes.try_into().unwrap());
+        }
+    }
+
+    Ok(())
+}
+
+// encode each value into a buffer, then write whole chunks of bytes at once
+fn write_chunks<W: Write>(write: &mut W, values: &[F80], encode: impl Fn(F80) -> [u8; 10]) -> Result<()> {
+    let mut buffer = [0_u8; 10 * CHUNK_SIZE];
+
+    for chunk in values.chunks(CHUNK_SIZE) {
+        let bytes = &mut buffer[.. 10 * chunk.len()];
+
+        for (&value, bytes) in chunk.iter().zip(bytes.chunks_exact_mut(10)) {
+            bytes.copy_from_slice(&encode(value));
+        }
+
+        write.write_all(bytes)?;
+    }
+
+    Ok(())
+}
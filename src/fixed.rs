@@ -0,0 +1,240 @@
+use crate::Endian;
+use crate::error::for_type;
+use crate::io::{ WriteEndian, ReadEndian, bytes };
+use std::io::{ Read, Write, Result };
+use std::convert::TryFrom;
+use std::fmt;
+
+
+/// A TrueType `Fixed` number, with 16 integer bits and 16 fraction bits.
+pub type Fixed16Dot16 = FixedI32<16>;
+
+/// A TrueType `F2Dot14` number, with 2 integer bits and 14 fraction bits.
+pub type F2Dot14 = FixedI16<14>;
+
+
+/// The error returned when converting a float that is not a number,
+/// or that does not fit into the range of a fixed point type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfRangeError;
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("float value is not a number or does not fit into the fixed point range")
+    }
+}
+
+impl std::error::Error for OutOfRangeError {}
+
+
+// exact powers of two, constructed from their bits
+#[inline]
+fn exp2_f64(exponent: i32) -> f64 {
+    f64::from_bits(((1023 + exponent) as u64) << 52)
+}
+
+#[inline]
+fn exp2_f32(exponent: i32) -> f32 {
+    f32::from_bits(((127 + exponent) as u32) << 23)
+}
+
+macro_rules! implement_fixed {
+    ($name: ident, $bits: ident, $signed: expr, $doc: expr) => {
+
+        #[doc = $doc]
+        ///
+        /// The number of fraction bits is `FRACTION`,
+        /// and all remaining bits of the underlying integer are integer bits.
+        /// Is stored and transferred as its underlying integer,
+        /// so reading and writing uses the same byte order rules as the integer.
+        ///
+        /// Converting to a float is exact whenever the float has enough mantissa bits,
+        /// and rounds to the nearest float otherwise.
+        /// Converting from a float rounds to the nearest fixed point value, with ties to even,
+        /// and fails if the value is not a number or out of range.
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(transparent)]
+        pub struct $name<const FRACTION: u32>($bits);
+
+        impl<const FRACTION: u32> $name<FRACTION> {
+
+            // evaluated at compile time whenever a value is converted
+            const VALID_FRACTION: () = assert!(FRACTION <= $bits::BITS, "too many fraction bits");
+
+            /// The number of fraction bits.
+            pub const FRACTION_BITS: u32 = FRACTION;
+
+            /// The number of integer bits, including the sign bit.
+            pub const INTEGER_BITS: u32 = $bits::BITS - FRACTION;
+
+            /// The smallest value.
+            pub const MIN: Self = $name($bits::MIN);
+
+            /// The largest value.
+            pub const MAX: Self = $name($bits::MAX);
+
+            /// Reinterpret the underlying integer as a fixed point number.
+            #[inline]
+            pub const fn from_bits(bits: $bits) -> Self {
+                $name(bits)
+            }
+
+            /// The underlying integer of this fixed point number.
+            #[inline]
+            pub const fn to_bits(self) -> $bits {
+                self.0
+            }
+
+            /// Convert to the nearest `f64`.
+            #[inline]
+            pub fn to_f64(self) -> f64 {
+                #[allow(clippy::let_unit_value)]
+                let () = Self::VALID_FRACTION;
+
+                // the multiplication by a power of two is exact, so this only rounds once
+                self.0 as f64 * exp2_f64(-(FRACTION as i32))
+            }
+
+            /// Convert to the nearest `f32`.
+            #[inline]
+            pub fn to_f32(self) -> f32 {
+                #[allow(clippy::let_unit_value)]
+                let () = Self::VALID_FRACTION;
+                self.0 as f32 * exp2_f32(-(FRACTION as i32))
+            }
+
+            /// Convert to the nearest fixed point number.
+            /// Returns an error if the value is not a number or out of range.
+            #[inline]
+            pub fn try_from_f64(value: f64) -> std::result::Result<Self, OutOfRangeError> {
+                #[allow(clippy::let_unit_value)]
+                let () = Self::VALID_FRACTION;
+
+                let scaled = (value * exp2_f64(FRACTION as i32)).round_ties_even();
+
+                let (start, end) =
+                    if $signed { (-exp2_f64($bits::BITS as i32 - 1), exp2_f64($bits::BITS as i32 - 1)) }
+                    else { (0.0, exp2_f64($bits::BITS as i32)) };
+
+                // also rejects nan, as all comparisons with nan are false
+                if scaled >= start && scaled < end { Ok($name(scaled as $bits)) }
+                else { Err(OutOfRangeError) }
+            }
+
+            /// Convert to the nearest fixed point number.
+            /// Returns an error if the value is not a number or out of range.
+            #[inline]
+            pub fn try_from_f32(value: f32) -> std::result::Result<Self, OutOfRangeError> {
+                Self::try_from_f64(f64::from(value))
+            }
+        }
+
+        impl<const FRACTION: u32> fmt::Display for $name<FRACTION> {
+            fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.to_f64(), formatter)
+            }
+        }
+
+        impl<const FRACTION: u32> From<$name<FRACTION>> for f64 {
+            #[inline]
+            fn from(value: $name<FRACTION>) -> Self {
+                value.to_f64()
+            }
+        }
+
+        impl<const FRACTION: u32> From<$name<FRACTION>> for f32 {
+            #[inline]
+            fn from(value: $name<FRACTION>) -> Self {
+                value.to_f32()
+            }
+        }
+
+        impl<const FRACTION: u32> TryFrom<f64> for $name<FRACTION> {
+            type Error = OutOfRangeError;
+
+            #[inline]
+            fn try_from(value: f64) -> std::result::Result<Self, OutOfRangeError> {
+                Self::try_from_f64(value)
+            }
+        }
+
+        impl<const FRACTION: u32> TryFrom<f32> for $name<FRACTION> {
+            type Error = OutOfRangeError;
+
+            #[inline]
+            fn try_from(value: f32) -> std::result::Result<Self, OutOfRangeError> {
+                Self::try_from_f32(value)
+            }
+        }
+
+        impl<const FRACTION: u32> Endian for $name<FRACTION> {
+            #[inline]
+            fn swap_bytes(&mut self) {
+                self.0 = self.0.swap_bytes();
+            }
+        }
+
+        impl<const FRACTION: u32> Endian for [$name<FRACTION>] {
+            #[inline]
+            fn swap_bytes(&mut self) {
+                // safe because the fixed point type is `repr(transparent)`
+                unsafe { bytes::cast_slice_mut::<_, $bits>(self) }.swap_bytes();
+            }
+        }
+
+        impl<W: Write, const FRACTION: u32> WriteEndian<$name<FRACTION>> for W {
+            #[inline]
+            fn write_as_little_endian(&mut self, value: &$name<FRACTION>) -> Result<()> {
+                self.write_as_little_endian(&value.0)
+            }
+
+            #[inline]
+            fn write_as_big_endian(&mut self, value: &$name<FRACTION>) -> Result<()> {
+                self.write_as_big_endian(&value.0)
+            }
+        }
+
+        impl<R: Read, const FRACTION: u32> ReadEndian<$name<FRACTION>> for R {
+            #[inline]
+            fn read_from_little_endian_into(&mut self, value: &mut $name<FRACTION>) -> Result<()> {
+                self.read_from_little_endian_into(&mut value.0).map_err(for_type::<$name<FRACTION>>)
+            }
+
+            #[inline]
+            fn read_from_big_endian_into(&mut self, value: &mut $name<FRACTION>) -> Result<()> {
+                self.read_from_big_endian_into(&mut value.0).map_err(for_type::<$name<FRACTION>>)
+            }
+        }
+
+        impl<W: Write, const FRACTION: u32> WriteEndian<[$name<FRACTION>]> for W {
+            fn write_as_little_endian(&mut self, value: &[$name<FRACTION>]) -> Result<()> {
+                self.write_as_little_endian(unsafe { bytes::cast_slice::<_, $bits>(value) })
+            }
+
+            fn write_as_big_endian(&mut self, value: &[$name<FRACTION>]) -> Result<()> {
+                self.write_as_big_endian(unsafe { bytes::cast_slice::<_, $bits>(value) })
+            }
+        }
+
+        impl<R: Read, const FRACTION: u32> ReadEndian<[$name<FRACTION>]> for R {
+            fn read_from_little_endian_into(&mut self, value: &mut [$name<FRACTION>]) -> Result<()> {
+                self.read_from_little_endian_into(unsafe { bytes::cast_slice_mut::<_, $bits>(value) })
+                    .map_err(for_type::<[$name<FRACTION>]>)
+            }
+
+            fn read_from_big_endian_into(&mut self, value: &mut [$name<FRACTION>]) -> Result<()> {
+                self.read_from_big_endian_into(unsafe { bytes::cast_slice_mut::<_, $bits>(value) })
+                    .map_err(for_type::<[$name<FRACTION>]>)
+            }
+        }
+    };
+}
+
+implement_fixed!(FixedI8, i8, true, "A signed fixed point number, stored as an `i8`.");
+implement_fixed!(FixedI16, i16, true, "A signed fixed point number, stored as an `i16`.");
+implement_fixed!(FixedI32, i32, true, "A signed fixed point number, stored as an `i32`.");
+implement_fixed!(FixedI64, i64, true, "A signed fixed point number, stored as an `i64`.");
+implement_fixed!(FixedU8, u8, false, "An unsigned fixed point number, stored as a `u8`.");
+implement_fixed!(FixedU16, u16, false, "An unsigned fixed point number, stored as a `u16`.");
+implement_fixed!(FixedU32, u32, false, "An unsigned fixed point number, stored as a `u32`.");
+implement_fixed!(FixedU64, u64, false, "An unsigned fixed point number, stored as a `u64`.");
@@ -0,0 +1,170 @@
+use crate::Endian;
+use crate::error::for_type;
+use crate::io::{ ReadEndian, CHUNK_SIZE, write_converted };
+use std::io::{ Read, Write, Result, Error, ErrorKind };
+
+
+/// Convert the bits of an IBM single precision hexadecimal float to an `f64`.
+/// This conversion is exact, as every IBM single precision float can be represented as an `f64`.
+///
+/// The IBM format consists of a sign bit, a 7-bit base 16 exponent biased by 64,
+/// and a 24-bit fraction without an implicit leading bit: `(-1)^sign * 0.fraction * 16^(exponent - 64)`.
+/// Unnormalized fractions are accepted.
+#[inline]
+pub fn ibm_f32_to_f64(bits: u32) -> f64 {
+    let negative = bits >> 31 != 0;
+    let exponent = ((bits >> 24) & 0x7F) as i32;
+    let fraction = f64::from(bits & 0x00FF_FFFF);
+
+    let magnitude = fraction * exp2_f64(4 * (exponent - 64) - 24);
+    if negative { -magnitude } else { magnitude }
+}
+
+/// Convert the bits of an IBM single precision hexadecimal float to an `f32`.
+///
+/// Rounds to the nearest `f32`, with ties to even. As the IBM format has a much larger range,
+/// values that are too large become infinite, and values that are too small become subnormal or zero.
+#[inline]
+pub fn ibm_f32_to_f32(bits: u32) -> f32 {
+    // the intermediate f64 is exact, so this rounds only once
+    ibm_f32_to_f64(bits) as f32
+}
+
+/// Convert an `f32` to the bits of a normalized IBM single precision hexadecimal float.
+///
+/// Every finite `f32` lies within the range of the IBM format,
+/// but the IBM format may have up to three fewer bits of precision,
+/// so the value is rounded to the nearest IBM float, with ties to even.
+/// Zero keeps its sign. Returns `None` for infinity and nan, which the IBM format cannot represent.
+#[inline]
+pub fn f32_to_ibm_f32(value: f32) -> Option<u32> {
+    if !value.is_finite() { return None; }
+
+    let bits = value.to_bits();
+    let sign = bits & 0x8000_0000;
+    let biased_exponent = ((bits >> 23) & 0xFF) as i32;
+    let stored_mantissa = bits & 0x007F_FFFF;
+
+    // the absolute value is `mantissa * 2^shift`
+    let (mantissa, shift) = match biased_exponent {
+        0 => (stored_mantissa, -149),
+        _ => (stored_mantissa | 0x0080_0000, biased_exponent - 150),
+    };
+
+    if mantissa == 0 { return Some(sign); }
+
+    // the value is below `2^top`, and the hexadecimal exponent must be at least `top / 4`
+    let top = shift + (32 - mantissa.leading_zeros() as i32);
+    let hex_exponent = (top + 3).div_euclid(4);
+    let fraction_shift = shift + 24 - 4 * hex_exponent;
+
+    let fraction = if fraction_shift >= 0 {
+        mantissa << fraction_shift
+    }
+    else {
+        // at most three bits are dropped, so rounding up can never carry into the next hexadecimal digit
+        let dropped = (-fraction_shift) as u32;
+        let remainder = mantissa & ((1 << dropped) - 1);
+        let half = 1 << (dropped - 1);
+        let truncated = mantissa >> dropped;
+
+        if remainder > half || (remainder == half && truncated & 1 == 1) { truncated + 1 }
+        else { truncated }
+    };
+
+    Some(sign | (((hex_exponent + 64) as u32) << 24) | fraction)
+}
+
+#[inline]
+fn exp2_f64(exponent: i32) -> f64 {
+    debug_assert!((-1022 ..= 1023).contains(&exponent), "exponent out of the normal f64 range");
+    f64::from_bits(((exponent + 1023) as u64) << 52)
+}
+
+
+/// A `std::io::Read` input stream which supports reading IBM single precision hexadecimal floats,
+/// as found in SEG-Y seismic files and legacy mainframe data, directly into slices of `f32`.
+///
+/// The values are read in large blocks using the fast `[u32]` slice path of `ReadEndian`,
+/// and then converted using `ibm_f32_to_f32`.
+///
+/// This extension trait is implemented for all `Read` types.
+pub trait ReadIbmFloats {
+
+    /// Read big endian IBM floats and convert them to `f32`. Consumes four bytes per element.
+    fn read_ibm_f32_from_big_endian_into(&mut self, values: &mut [f32]) -> Result<()>;
+
+    /// Read little endian IBM floats and convert them to `f32`. Consumes four bytes per element.
+    fn read_ibm_f32_from_little_endian_into(&mut self, values: &mut [f32]) -> Result<()>;
+}
+
+/// A `std::io::Write` output stream which supports writing slices of `f32` as IBM single precision hexadecimal floats.
+///
+/// The values are converted using `f32_to_ibm_f32` into a buffer on the stack,
+/// which is converted to the byte order in place and written in large blocks.
+/// Returns an `InvalidInput` error if a value is infinite or nan,
+/// in which case the preceding values may already have been written.
+///
+/// This extension trait is implemented for all `Write` types.
+pub trait WriteIbmFloats {
+
+    /// Convert the values to IBM floats and write them as big endian. Produces four bytes per element.
+    fn write_ibm_f32_as_big_endian(&mut self, values: &[f32]) -> Result<()>;
+
+    /// Convert the values to IBM floats and write them as little endian. Produces four bytes per element.
+    fn write_ibm_f32_as_little_endian(&mut self, values: &[f32]) -> Result<()>;
+}
+
+impl<R: Read> ReadIbmFloats for R {
+    fn read_ibm_f32_from_big_endian_into(&mut self, values: &mut [f32]) -> Result<()> {
+        read_ibm(self, values, |read, bits| read.read_from_big_endian_into(bits))
+    }
+
+    fn read_ibm_f32_from_little_endian_into(&mut self, values: &mut [f32]) -> Result<()> {
+        read_ibm(self, values, |read, bits| read.read_from_little_endian_into(bits))
+    }
+}
+
+impl<W: Write> WriteIbmFloats for W {
+    fn write_ibm_f32_as_big_endian(&mut self, values: &[f32]) -> Result<()> {
+        write_ibm(self, values, |bits| bits.convert_current_to_big_endian())
+    }
+
+    fn write_ibm_f32_as_little_endian(&mut self, values: &[f32]) -> Result<()> {
+        write_ibm(self, values, |bits| bits.convert_current_to_little_endian())
+    }
+}
+
+fn read_ibm<R: Read>(read: &mut R, values: &mut [f32], read_chunk: impl Fn(&mut R, &mut [u32]) -> Result<()>) -> Result<()> {
+    let mut buffer = [0_u32; CHUNK_SIZE];
+
+    for chunk in values.chunks_mut(CHUNK_SIZE) {
+        let bits = &mut buffer[.. chunk.len()];
+        read_chunk(read, bits).map_err(for_type::<[f32]>)?;
+
+        for (value, &bits) in chunk.iter_mut().zip(bits.iter()) {
+            *value = ibm_f32_to_f32(bits);
+        }
+    }
+
+    Ok(())
+}
+
+// writes the values up to the first one that cannot be converted
+fn write_ibm<W: Write>(write: &mut W, values: &[f32], convert_byte_order: impl Fn(&mut [u32])) -> Result<()> {
+    let mut invalid = false;
+
+    let bits = values.iter().map_while(|&value| {
+        let bits = f32_to_ibm_f32(value);
+        invalid = bits.is_none();
+        bits
+    });
+
+    unsafe { write_converted(write, bits, convert_byte_order)?; }
+
+    if invalid {
+        return Err(Error::new(ErrorKind::InvalidInput, "infinity and nan cannot be represented as IBM floats"));
+    }
+
+    Ok(())
+}
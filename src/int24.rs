@@ -0,0 +1,347 @@
+use crate::Endian;
+use crate::io::{ WriteEndian, ReadEndian, CHUNK_SIZE };
+use std::io::{ Read, Write, Result, Error, ErrorKind };
+
+
+/// An unsigned 24-bit integer, as used for PCM audio samples and packed length fields.
+/// Is stored in the lower bits of a `u32`, but takes up three bytes when written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U24(u32);
+
+/// A signed 24-bit integer, as used for PCM audio samples.
+/// Is stored as a sign-extended `i32`, but takes up three bytes when written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct I24(i32);
+
+impl U24 {
+
+    /// The smallest value, zero.
+    pub const MIN: Self = U24(0);
+
+    /// The largest value, `2^24 - 1`.
+    pub const MAX: Self = U24(0x00FF_FFFF);
+
+    /// The number of bits in this type.
+    pub const BITS: u32 = 24;
+
+    /// Returns `None` if the value does not fit into 24 bits.
+    #[inline]
+    pub const fn new(value: u32) -> Option<Self> {
+        if value <= Self::MAX.0 { Some(U24(value)) } else { None }
+    }
+
+    /// Discards the upper 8 bits of the value.
+    #[inline]
+    pub const fn wrapping_from(value: u32) -> Self {
+        U24(value & Self::MAX.0)
+    }
+
+    /// The numeric value.
+    #[inline]
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+
+    /// Create a value from its little endian byte representation.
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; 3]) -> Self {
+        U24(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]))
+    }
+
+    /// Create a value from its big endian byte representation.
+    #[inline]
+    pub const fn from_be_bytes(bytes: [u8; 3]) -> Self {
+        U24(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]))
+    }
+
+    /// The little endian byte representation of this value.
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; 3] {
+        let [a, b, c, _] = self.0.to_le_bytes();
+        [a, b, c]
+    }
+
+    /// The big endian byte representation of this value.
+    #[inline]
+    pub const fn to_be_bytes(self) -> [u8; 3] {
+        let [_, a, b, c] = self.0.to_be_bytes();
+        [a, b, c]
+    }
+}
+
+impl I24 {
+
+    /// The smallest value, `-2^23`.
+    pub const MIN: Self = I24(-0x0080_0000);
+
+    /// The largest value, `2^23 - 1`.
+    pub const MAX: Self = I24(0x007F_FFFF);
+
+    /// The number of bits in this type.
+    pub const BITS: u32 = 24;
+
+    /// Returns `None` if the value does not fit into 24 bits.
+    #[inline]
+    pub const fn new(value: i32) -> Option<Self> {
+        if value >= Self::MIN.0 && value <= Self::MAX.0 { Some(I24(value)) } else { None }
+    }
+
+    /// Discards the upper 8 bits of the value and sign-extends the remaining 24 bits.
+    #[inline]
+    pub const fn wrapping_from(value: i32) -> Self {
+        I24((value << 8) >> 8)
+    }
+
+    /// The numeric value.
+    #[inline]
+    pub const fn get(self) -> i32 {
+        self.0
+    }
+
+    /// Create a value from its little endian byte representation.
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; 3]) -> Self {
+        I24(i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8)
+    }
+
+    /// Create a value from its big endian byte representation.
+    #[inline]
+    pub const fn from_be_bytes(bytes: [u8; 3]) -> Self {
+        I24(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], 0]) >> 8)
+    }
+
+    /// The little endian byte representation of this value.
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; 3] {
+        let [a, b, c, _] = self.0.to_le_bytes();
+        [a, b, c]
+    }
+
+    /// The big endian byte representation of this value.
+    #[inline]
+    pub const fn to_be_bytes(self) -> [u8; 3] {
+        let [_, a, b, c] = self.0.to_be_bytes();
+        [a, b, c]
+    }
+}
+
+macro_rules! implement_lossless_conversions {
+    ($type: ident : $inner: ident, from $( $smaller: ident ),* ; into $( $larger: ident ),* ) => {
+        $(
+            impl From<$smaller> for $type {
+                #[inline]
+                fn from(value: $smaller) -> Self {
+                    $type($inner::from(value))
+                }
+            }
+        )*
+
+        $(
+            impl From<$type> for $larger {
+                #[inline]
+                fn from(value: $type) -> Self {
+                    $larger::from(value.0)
+                }
+            }
+        )*
+    };
+}
+
+implement_lossless_conversions!(U24: u32, from u8, u16; into u32, u64, u128, i64, i128);
+implement_lossless_conversions!(I24: i32, from u8, i8, u16, i16; into i32, i64, i128);
+
+impl From<U24> for i32 {
+    #[inline]
+    fn from(value: U24) -> Self {
+        value.0 as i32
+    }
+}
+
+impl Endian for U24 {
+    #[inline]
+    fn swap_bytes(&mut self) {
+        self.0 = self.0.swap_bytes() >> 8;
+    }
+}
+
+impl Endian for I24 {
+    #[inline]
+    fn swap_bytes(&mut self) {
+        // the arithmetic shift sign-extends the new most significant byte
+        self.0 = (self.0.swap_bytes() & !0xFF) >> 8;
+    }
+}
+
+impl Endian for [U24] {
+    fn swap_bytes(&mut self) {
+        for number in self.iter_mut() {
+            number.swap_bytes();
+        }
+    }
+}
+
+impl Endian for [I24] {
+    fn swap_bytes(&mut self) {
+        for number in self.iter_mut() {
+            number.swap_bytes();
+        }
+    }
+}
+
+
+/// Reads packed 3-byte integers from a `std::io::Read` stream into slices of 32-bit integers.
+///
+/// This extension trait is implemented for all `Read` types.
+pub trait ReadInt24: Read {
+
+    /// Read signed little endian 24-bit integers, sign-extending them into the slice.
+    /// Consumes three bytes per element.
+    fn read_i24_from_little_endian_into(&mut self, values: &mut [i32]) -> Result<()> {
+        read_packed(self, values, |bytes| I24::from_le_bytes(bytes).get())
+    }
+
+    /// Read signed big endian 24-bit integers, sign-extending them into the slice.
+    /// Consumes three bytes per element.
+    fn read_i24_from_big_endian_into(&mut self, values: &mut [i32]) -> Result<()> {
+        read_packed(self, values, |bytes| I24::from_be_bytes(bytes).get())
+    }
+
+    /// Read unsigned little endian 24-bit integers into the slice.
+    /// Consumes three bytes per element.
+    fn read_u24_from_little_endian_into(&mut self, values: &mut [u32]) -> Result<()> {
+        read_packed(self, values, |bytes| U24::from_le_bytes(bytes).get())
+    }
+
+    /// Read unsigned big endian 24-bit integers into the slice.
+    /// Consumes three bytes per element.
+    fn read_u24_from_big_endian_into(&mut self, values: &mut [u32]) -> Result<()> {
+        read_packed(self, values, |bytes| U24::from_be_bytes(bytes).get())
+    }
+}
+
+/// Writes slices of 32-bit integers to a `std::io::Write` stream as packed 3-byte integers.
+///
+/// This extension trait is implemented for all `Write` types.
+/// Returns an `InvalidInput` error if a value does not fit into 24 bits.
+/// In that case, the preceding values may already have been written.
+pub trait WriteInt24: Write {
+
+    /// Write each value as a signed little endian 24-bit integer.
+    fn write_i24_as_little_endian(&mut self, values: &[i32]) -> Result<()> {
+        write_packed(self, values, |value| I24::new(value).map(I24::to_le_bytes))
+    }
+
+    /// Write each value as a signed big endian 24-bit integer.
+    fn write_i24_as_big_endian(&mut self, values: &[i32]) -> Result<()> {
+        write_packed(self, values, |value| I24::new(value).map(I24::to_be_bytes))
+    }
+
+    /// Write each value as an unsigned little endian 24-bit integer.
+    fn write_u24_as_little_endian(&mut self, values: &[u32]) -> Result<()> {
+        write_packed(self, values, |value| U24::new(value).map(U24::to_le_bytes))
+    }
+
+    /// Write each value as an unsigned big endian 24-bit integer.
+    fn write_u24_as_big_endian(&mut self, values: &[u32]) -> Result<()> {
+        write_packed(self, values, |value| U24::new(value).map(U24::to_be_bytes))
+    }
+}
+
+impl<R: Read + ?Sized> ReadInt24 for R {}
+impl<W: Write + ?Sized> WriteInt24 for W {}
+
+
+#[inline]
+fn read_packed<R: Read + ?Sized, T: Copy>(read: &mut R, values: &mut [T], decode: impl Fn([u8; 3]) -> T) -> Result<()> {
+    let mut buffer = [0_u8; 3 * CHUNK_SIZE];
+
+    for chunk in values.chunks_mut(CHUNK_SIZE) {
+        let bytes = &mut buffer[.. 3 * chunk.len()];
+        crate::error::read_exact(read, bytes, std::any::type_name::<[T]>())?;
+
+        for (value, bytes) in chunk.iter_mut().zip(bytes.chunks_exact(3)) {
+            *value = decode([bytes[0], bytes[1], bytes[2]]);
+        }
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn write_packed<W: Write + ?Sized, T: Copy>(write: &mut W, values: &[T], encode: impl Fn(T) -> Option<[u8; 3]>) -> Result<()> {
+    let mut buffer = [0_u8; 3 * CHUNK_SIZE];
+
+    for chunk in values.chunks(CHUNK_SIZE) {
+        let bytes = &mut buffer[.. 3 * chunk.len()];
+
+        for (&value, bytes) in chunk.iter().zip(bytes.chunks_exact_mut(3)) {
+            let encoded = encode(value).ok_or_else(|| Error::new(
+                ErrorKind::InvalidInput, "integer value does not fit into 24 bits"
+            ))?;
+
+            bytes.copy_from_slice(&encoded);
+        }
+
+        write.write_all(bytes)?;
+    }
+
+    Ok(())
+}
+
+
+macro_rules! implement_int24_io {
+    ($type: ident) => {
+        impl<W: Write> WriteEndian<$type> for W {
+            #[inline]
+            fn write_as_little_endian(&mut self, value: &$type) -> Result<()> {
+                self.write_all(&value.to_le_bytes())
+            }
+
+            #[inline]
+            fn write_as_big_endian(&mut self, value: &$type) -> Result<()> {
+                self.write_all(&value.to_be_bytes())
+            }
+        }
+
+        impl<R: Read> ReadEndian<$type> for R {
+            #[inline]
+            fn read_from_little_endian_into(&mut self, value: &mut $type) -> Result<()> {
+                let mut bytes = [0_u8; 3];
+                crate::error::read_exact(self, &mut bytes, std::any::type_name::<$type>())?;
+                *value = $type::from_le_bytes(bytes);
+                Ok(())
+            }
+
+            #[inline]
+            fn read_from_big_endian_into(&mut self, value: &mut $type) -> Result<()> {
+                let mut bytes = [0_u8; 3];
+                crate::error::read_exact(self, &mut bytes, std::any::type_name::<$type>())?;
+                *value = $type::from_be_bytes(bytes);
+                Ok(())
+            }
+        }
+
+        impl<W: Write> WriteEndian<[$type]> for W {
+            fn write_as_little_endian(&mut self, value: &[$type]) -> Result<()> {
+                write_packed(self, value, |value| Some(value.to_le_bytes()))
+            }
+
+            fn write_as_big_endian(&mut self, value: &[$type]) -> Result<()> {
+                write_packed(self, value, |value| Some(value.to_be_bytes()))
+            }
+        }
+
+        impl<R: Read> ReadEndian<[$type]> for R {
+            fn read_from_little_endian_into(&mut self, value: &mut [$type]) -> Result<()> {
+                read_packed(self, value, $type::from_le_bytes)
+            }
+
+            fn read_from_big_endian_into(&mut self, value: &mut [$type]) -> Result<()> {
+                read_packed(self, value, $type::from_be_bytes)
+            }
+        }
+    };
+}
+
+implement_int24_io!(U24);
+implement_int24_io!(I24);
@@ -0,0 +1,77 @@
+// prevents other crates from implementing the integer traits
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Any primitive unsigned integer, including `usize`.
+/// Used by the encodings that need to process integers generically, like `varint`.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Unsigned: Copy + Default + sealed::Sealed {
+
+    /// The signed integer with the same number of bits.
+    type Signed: Signed<Unsigned = Self>;
+
+    /// The number of bits in this type.
+    const BITS: u32;
+
+    /// Zero-extend this value.
+    #[doc(hidden)]
+    fn to_u128(self) -> u128;
+
+    /// Discard all bits that do not fit into this type.
+    #[doc(hidden)]
+    fn from_u128_truncated(bits: u128) -> Self;
+}
+
+/// Any primitive signed integer, including `isize`.
+/// Used by the encodings that need to process integers generically, like `varint`.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Signed: Copy + Default + sealed::Sealed {
+
+    /// The unsigned integer with the same number of bits.
+    type Unsigned: Unsigned<Signed = Self>;
+
+    /// The number of bits in this type.
+    const BITS: u32;
+
+    /// Sign-extend this value.
+    #[doc(hidden)]
+    fn to_i128(self) -> i128;
+
+    /// Discard all bits that do not fit into this type.
+    #[doc(hidden)]
+    fn from_i128_truncated(bits: i128) -> Self;
+}
+
+macro_rules! implement_integers {
+    ($( $unsigned: ident : $signed: ident ),* ) => {
+        $(
+            impl sealed::Sealed for $unsigned {}
+            impl sealed::Sealed for $signed {}
+
+            #[allow(trivial_numeric_casts)]
+            impl Unsigned for $unsigned {
+                type Signed = $signed;
+                const BITS: u32 = $unsigned::BITS;
+
+                #[inline] fn to_u128(self) -> u128 { self as u128 }
+                #[inline] fn from_u128_truncated(bits: u128) -> Self { bits as $unsigned }
+            }
+
+            #[allow(trivial_numeric_casts)]
+            impl Signed for $signed {
+                type Unsigned = $unsigned;
+                const BITS: u32 = $signed::BITS;
+
+                #[inline] fn to_i128(self) -> i128 { self as i128 }
+                #[inline] fn from_i128_truncated(bits: i128) -> Self { bits as $signed }
+            }
+        )*
+    };
+}
+
+implement_integers! {
+    u8: i8, u16: i16, u32: i32, u64: i64, u128: i128, usize: isize
+}
@@ -57,12 +57,96 @@
 //! # Ok::<(), std::io::Error>(())
 //! ```
 //!
+//! ### Other Types
+//! Reading and writing is also implemented for `bool`, `char` and the `NonZero` integers.
+//! As not every sequence of bytes is a valid value for these types,
+//! reading them returns an `InvalidData` error for invalid bytes.
+//! The wrappers `Wrapping`, `Saturating` and `Option<NonZero>` are supported
+//! wherever their inner integers are, including slices.
+//!
+//! The mixed-endian layouts of PDP-11 integers and ARM FPA doubles are supported
+//! for `u32`, `i32`, `f32` and `f64` through `MixedEndian`, `WriteMixedEndian` and `ReadMixedEndian`.
+//!
+//! The 24-bit integers in the `int24` module and the fixed point numbers in the `fixed` module
+//! can also be converted, read and written.
+//! The 80-bit extended precision floats in the `f80` module can be read and written, but not converted in place,
+//! as their in-memory representation differs from the ten bytes that are stored.
+//!
+//! With the `half` feature, the half precision floats `half::f16` and `half::bf16` are supported as well,
+//! and can also be read directly into slices of `f32`.
+//!
+//! Strings can be read and written as UTF-16 or UTF-32 using the `strings` module,
+//! which also detects byte order marks, using the runtime `ByteOrder` type.
+//! It also handles fixed-size and null-terminated byte string fields, like `char name[32]` in C headers.
+//!
+//! Legacy IBM hexadecimal floats can be read directly into slices of `f32` using the `ibm` module,
+//! and packed binary coded decimals can be read and written using the `bcd` module.
+//!
+//! The address types in `std::net` can be read and written as well.
+//! Ip addresses are always stored in their canonical network octet order,
+//! while ports and flow info use the requested byte order.
+//!
+//! ### Read Checked Values
+//! ```rust
+//! use std::num::NonZeroU32;
+//! use lebe::prelude::*;
+//! let mut reader: &[u8] = &[1, 0,0,0,65, 0,0,0,7];
+//!
+//! let flag: bool = reader.read_from_big_endian()?;
+//! let letter: char = reader.read_from_big_endian()?;
+//!
+//! // `NonZeroU32` has no default value, so it is read into an existing value
+//! let mut count = NonZeroU32::MIN;
+//! reader.read_from_big_endian_into(&mut count)?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! ### Network Addresses
+//! ```rust
+//! use std::net::{Ipv4Addr, SocketAddrV4};
+//! use lebe::prelude::*;
+//! let mut writer: Vec<u8> = Vec::new();
+//!
+//! writer.write_as_big_endian(&SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8080))?;
+//! assert_eq!(writer, [127,0,0,1, 0x1F,0x90]);
+//!
+//! // addresses have no default value, so they are read into an existing value
+//! let mut address = SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0);
+//! writer.as_slice().read_from_big_endian_into(&mut address)?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! ### Fill In Sizes Later
+//! ```rust
+//! use lebe::ByteOrder;
+//! use lebe::prelude::*;
+//! use lebe::positioned::PositionedWriter;
+//! let mut writer = PositionedWriter::new(Vec::new());
+//!
+//! let size = writer.reserve::<u32>(ByteOrder::LittleEndian)?;
+//! writer.write_as_little_endian(&[1_u16, 2, 3][..])?;
+//! writer.fill(size, &6)?;
+//!
+//! assert_eq!(writer.finish()?, [6,0,0,0, 1,0, 2,0, 3,0]);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+
 
+pub use error::Error;
 
 /// Exports some of the most common types.
 pub mod prelude {
-    pub use super::Endian;
-    pub use super::io::{ WriteEndian, ReadEndian, ReadPrimitive };
+    pub use super::{ Endian, MixedEndian };
+    pub use super::io::{ WriteEndian, ReadEndian, ReadPrimitive, ReadPartial, TryReadEndian, TryReadRecord, ReadChunk, ReadPadding, SeekPadding, WritePadding, IterEndian, WriteIterEndian, BufReadEndian, WriteMixedEndian, ReadMixedEndian, WritePacked, ReadPacked };
+    pub use super::int24::{ WriteInt24, ReadInt24 };
+    pub use super::varint::{ WriteVarInt, ReadVarInt };
+    pub use super::strings::{ WriteUnicode, ReadUnicode, WriteByteStrings, ReadByteStrings };
+    pub use super::ibm::{ WriteIbmFloats, ReadIbmFloats };
+    pub use super::bcd::{ WriteBcd, ReadBcd };
+
+    #[cfg(feature = "half")]
+    pub use super::io::ReadHalfFloats;
 }
 
 /// Represents values that can swap their bytes to reverse their endianness.
@@ -74,6 +158,7 @@ pub mod prelude {
 ///
 /// For the types `u8`, `i8`, `&[u8]` and `&[i8]`, this trait will never transform any data,
 /// as they are just implemented for completeness.
+#[allow(clippy::wrong_self_convention)]
 pub trait Endian {
 
     /// Swaps all bytes in this value, inverting its endianness.
@@ -141,6 +226,30 @@ pub trait Endian {
 }
 
 
+/// A byte order that is only known at runtime,
+/// for example after detecting a byte order mark.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+
+    /// The least significant byte comes first.
+    LittleEndian,
+
+    /// The most significant byte comes first.
+    BigEndian,
+}
+
+impl ByteOrder {
+
+    /// The byte order of the current machine.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: ByteOrder = ByteOrder::LittleEndian;
+
+    /// The byte order of the current machine.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: ByteOrder = ByteOrder::BigEndian;
+}
+
+
 // call a macro for each argument
 macro_rules! call_single_arg_macro_for_each {
     ($macro: ident, $( $arguments: ident ),* ) => {
@@ -186,6 +295,15 @@ macro_rules! implement_float_primitive_by_bits {
 implement_float_primitive_by_bits!(f32);
 implement_float_primitive_by_bits!(f64);
 
+#[cfg(feature = "half")]
+use half::{ f16, bf16 };
+
+#[cfg(feature = "half")]
+call_single_arg_macro_for_each! {
+    implement_float_primitive_by_bits,
+    f16, bf16
+}
+
 macro_rules! implement_slice_by_element {
     ($type: ident) => {
         impl Endian for [$type] {
@@ -205,37 +323,233 @@ call_single_arg_macro_for_each! {
     f64, f32
 }
 
+#[cfg(feature = "half")]
+call_single_arg_macro_for_each! {
+    implement_slice_by_element,
+    f16, bf16
+}
+
+// implement this interface for wrappers with the same memory layout as their inner value,
+// which allows converting a slice of wrappers by converting the slice of inner values
+macro_rules! implement_transparent_wrapper_endian {
+    ($wrapper: ident) => {
+        impl<T: Endian> Endian for std::num::$wrapper<T> {
+            #[inline]
+            fn swap_bytes(&mut self) {
+                self.0.swap_bytes();
+            }
+        }
+
+        impl<T> Endian for [std::num::$wrapper<T>] where [T]: Endian {
+            #[inline]
+            fn swap_bytes(&mut self) {
+                // safe because the wrapper is `repr(transparent)`
+                let inner = unsafe {
+                    std::slice::from_raw_parts_mut(self.as_mut_ptr() as *mut T, self.len())
+                };
+
+                inner.swap_bytes();
+            }
+        }
+    };
+}
+
+call_single_arg_macro_for_each! {
+    implement_transparent_wrapper_endian,
+    Wrapping, Saturating
+}
+
+// implement this interface for optional non-zero integers,
+// which have the same memory layout as their primitive, where `None` is zero
+macro_rules! implement_optional_non_zero_endian {
+    ($( $type: ident : $primitive: ident ),* ) => {
+        $(
+            impl Endian for Option<std::num::$type> {
+                #[inline]
+                fn swap_bytes(&mut self) {
+                    let primitive = self.map_or(0, std::num::$type::get);
+                    *self = std::num::$type::new(primitive.swap_bytes());
+                }
+            }
+
+            impl Endian for [Option<std::num::$type>] {
+                #[inline]
+                fn swap_bytes(&mut self) {
+                    // safe because `Option<NonZero>` is guaranteed to have the same layout as its primitive
+                    let primitives = unsafe {
+                        std::slice::from_raw_parts_mut(self.as_mut_ptr() as *mut $primitive, self.len())
+                    };
+
+                    primitives.swap_bytes();
+                }
+            }
+        )*
+    };
+}
+
+implement_optional_non_zero_endian! {
+    NonZeroU8: u8, NonZeroU16: u16, NonZeroU32: u32, NonZeroU64: u64, NonZeroU128: u128,
+    NonZeroI8: i8, NonZeroI16: i16, NonZeroI32: i32, NonZeroI64: i64, NonZeroI128: i128
+}
+
+/// Represents values that can be stored in a mixed-endian layout,
+/// where the two halves of the value are swapped compared to little endian or big endian.
+///
+/// The word-swapped little endian layout is used for 32-bit values by the PDP-11,
+/// where `0x0A0B0C0D` is stored as `0B 0A 0D 0C`, and for `f64` by the ARM FPA.
+/// The word-swapped big endian layout stores `0x0A0B0C0D` as `0C 0D 0A 0B`.
+///
+/// Implemented for `u32`, `i32`, `f32` and `f64`, and slices of those.
+#[allow(clippy::wrong_self_convention)]
+pub trait MixedEndian: Endian {
+
+    /// Swaps the upper and the lower half of this value.
+    fn swap_words(&mut self);
+
+    /// Converts this value from the current byte order to word-swapped little endian.
+    #[inline] fn convert_current_to_word_swapped_little_endian(&mut self) {
+        self.convert_current_to_little_endian();
+        self.swap_words();
+    }
+
+    /// Converts this value from the current byte order to word-swapped big endian.
+    #[inline] fn convert_current_to_word_swapped_big_endian(&mut self) {
+        self.convert_current_to_big_endian();
+        self.swap_words();
+    }
+
+    /// Converts this value from word-swapped little endian to the current byte order.
+    #[inline] fn convert_word_swapped_little_endian_to_current(&mut self) {
+        self.swap_words();
+        self.convert_little_endian_to_current();
+    }
+
+    /// Converts this value from word-swapped big endian to the current byte order.
+    #[inline] fn convert_word_swapped_big_endian_to_current(&mut self) {
+        self.swap_words();
+        self.convert_big_endian_to_current();
+    }
+
+    /// Converts this value from the current byte order to word-swapped little endian.
+    #[inline] fn from_current_into_word_swapped_little_endian(mut self) -> Self where Self: Sized {
+        self.convert_current_to_word_swapped_little_endian();
+        self
+    }
+
+    /// Converts this value from the current byte order to word-swapped big endian.
+    #[inline] fn from_current_into_word_swapped_big_endian(mut self) -> Self where Self: Sized {
+        self.convert_current_to_word_swapped_big_endian();
+        self
+    }
+
+    /// Converts this value from word-swapped little endian to the current byte order.
+    #[inline] fn from_word_swapped_little_endian_into_current(mut self) -> Self where Self: Sized {
+        self.convert_word_swapped_little_endian_to_current();
+        self
+    }
+
+    /// Converts this value from word-swapped big endian to the current byte order.
+    #[inline] fn from_word_swapped_big_endian_into_current(mut self) -> Self where Self: Sized {
+        self.convert_word_swapped_big_endian_to_current();
+        self
+    }
+}
+
+// swapping the halves is a rotation by half of the bits, regardless of the byte order of the machine
+macro_rules! implement_mixed_endian {
+    ($( $type: ident ),* ) => {
+        $(
+            impl MixedEndian for $type {
+                #[inline]
+                fn swap_words(&mut self) {
+                    *self = self.rotate_left(Self::BITS / 2);
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! implement_mixed_endian_float_by_bits {
+    ($( $type: ident : $bits: ident ),* ) => {
+        $(
+            impl MixedEndian for $type {
+                #[inline]
+                fn swap_words(&mut self) {
+                    *self = Self::from_bits(self.to_bits().rotate_left($bits::BITS / 2));
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! implement_mixed_endian_slice_by_element {
+    ($( $type: ident ),* ) => {
+        $(
+            impl MixedEndian for [$type] {
+                fn swap_words(&mut self) {
+                    for number in self.iter_mut() {
+                        number.swap_words();
+                    }
+                }
+            }
+        )*
+    };
+}
+
+implement_mixed_endian!(u32, i32);
+implement_mixed_endian_float_by_bits!(f32: u32, f64: u64);
+implement_mixed_endian_slice_by_element!(u32, i32, f32, f64);
+
 /// Easily write primitives and slices of primitives to
 /// binary `std::io::Write` streams and easily read from binary `std::io::Read` streams.
 ///
 /// Also contains the unsafe `bytes` module for reinterpreting values as byte slices and vice versa.
 pub mod io {
-    use super::Endian;
-    use std::io::{Read, Write, Result};
+    use super::{ Endian, MixedEndian };
+    use super::integer::Unsigned;
+    use super::error::for_type;
+    use std::io::{Read, BufRead, Write, Seek, SeekFrom, Result};
+
+    #[cfg(feature = "half")]
+    use half::{ f16, bf16 };
 
     /// Reinterpret values as byte slices and byte slices as values unsafely.
+    ///
+    /// # Safety
+    /// All functions in this module reinterpret memory without any checks.
+    /// Values must not contain padding bytes, and any bytes that are read
+    /// must form a valid value of the target type (for example, `bool` or `char` must not be used here).
     pub mod bytes {
         use std::io::{Read, Write, Result};
 
         /// View this slice of values as a slice of bytes.
+        ///
+        /// # Safety
+        /// See the module documentation.
         #[inline]
         pub unsafe fn slice_as_bytes<T>(value: &[T]) -> &[u8] {
             std::slice::from_raw_parts(
                 value.as_ptr() as *const u8,
-                value.len() * std::mem::size_of::<T>()
+                std::mem::size_of_val(value)
             )
         }
 
         /// View this slice of values as a mutable slice of bytes.
+        ///
+        /// # Safety
+        /// See the module documentation.
         #[inline]
         pub unsafe fn slice_as_bytes_mut<T>(value: &mut [T]) -> &mut [u8] {
             std::slice::from_raw_parts_mut(
                 value.as_mut_ptr() as *mut u8,
-                value.len() * std::mem::size_of::<T>()
+                std::mem::size_of_val(value)
             )
         }
 
         /// View this reference as a slice of bytes.
+        ///
+        /// # Safety
+        /// See the module documentation.
         #[inline]
         pub unsafe fn value_as_bytes<T: Sized>(value: &T) -> &[u8] {
             std::slice::from_raw_parts(
@@ -245,6 +559,9 @@ pub mod io {
         }
 
         /// View this reference as a mutable slice of bytes.
+        ///
+        /// # Safety
+        /// See the module documentation.
         #[inline]
         pub unsafe fn value_as_bytes_mut<T: Sized>(value: &mut T) ->&mut [u8] {
             std::slice::from_raw_parts_mut(
@@ -253,28 +570,62 @@ pub mod io {
             )
         }
 
+        /// View this slice of values as a slice of another type with the same memory layout.
+        ///
+        /// # Safety
+        /// See the module documentation. Additionally, `T` and `U` must have the same size and alignment.
+        #[inline]
+        pub unsafe fn cast_slice<T, U>(value: &[T]) -> &[U] {
+            debug_assert_eq!(std::mem::size_of::<T>(), std::mem::size_of::<U>());
+            debug_assert_eq!(std::mem::align_of::<T>(), std::mem::align_of::<U>());
+            std::slice::from_raw_parts(value.as_ptr() as *const U, value.len())
+        }
+
+        /// View this mutable slice of values as a mutable slice of another type with the same memory layout.
+        ///
+        /// # Safety
+        /// See the module documentation. Additionally, `T` and `U` must have the same size and alignment.
+        #[inline]
+        pub unsafe fn cast_slice_mut<T, U>(value: &mut [T]) -> &mut [U] {
+            debug_assert_eq!(std::mem::size_of::<T>(), std::mem::size_of::<U>());
+            debug_assert_eq!(std::mem::align_of::<T>(), std::mem::align_of::<U>());
+            std::slice::from_raw_parts_mut(value.as_mut_ptr() as *mut U, value.len())
+        }
+
         /// View this slice as a mutable slice of bytes and write it.
+        ///
+        /// # Safety
+        /// See the module documentation.
         #[inline]
         pub unsafe fn write_slice<T>(write: &mut impl Write, value: &[T]) -> Result<()> {
             write.write_all(slice_as_bytes(value))
         }
 
         /// Read a slice of bytes into the specified slice.
+        ///
+        /// # Safety
+        /// See the module documentation.
         #[inline]
         pub unsafe fn read_slice<T>(read: &mut impl Read, value: &mut [T]) -> Result<()> {
-            read.read_exact(slice_as_bytes_mut(value))
+            crate::error::read_exact(read, slice_as_bytes_mut(value), std::any::type_name::<[T]>())
         }
 
         /// View this reference as a mutable slice of bytes and write it.
+        ///
+        /// # Safety
+        /// See the module documentation.
         #[inline]
         pub unsafe fn write_value<T: Sized>(write: &mut impl Write, value: &T) -> Result<()> {
             write.write_all(value_as_bytes(value))
         }
 
         /// Read a slice of bytes into the specified reference.
+        ///
+        /// # Safety
+        /// See the module documentation.
         #[inline]
         pub unsafe fn read_value<T: Sized>(read: &mut impl Read, value: &mut T) -> Result<()> {
-            read.read_exact(value_as_bytes_mut(value))
+            crate::error::read_exact(read, value_as_bytes_mut(value), std::any::type_name::<T>())
         }
     }
 
@@ -426,16 +777,19 @@ pub mod io {
         f32, f64
     }
 
+    #[cfg(feature = "half")]
+    call_single_arg_macro_for_each! {
+        implement_simple_primitive_write,
+        f16, bf16
+    }
+
 
     macro_rules! implement_slice_io {
         ($type: ident) => {
             impl<W: Write> WriteEndian<[$type]> for W {
                 fn write_as_little_endian(&mut self, value: &[$type]) -> Result<()> {
-                    #[cfg(target_endian = "big")] {
-                        for number in value { // TODO SIMD!
-                            self.write_as_little_endian(number)?;
-                        }
-                    }
+                    #[cfg(target_endian = "big")]
+                    unsafe { write_converted(self, value.iter().copied(), |chunk| chunk.convert_current_to_little_endian())?; }
 
                     // else write whole slice
                     #[cfg(target_endian = "little")]
@@ -445,11 +799,8 @@ pub mod io {
                 }
 
                 fn write_as_big_endian(&mut self, value: &[$type]) -> Result<()> {
-                    #[cfg(target_endian = "little")] {
-                        for number in value { // TODO SIMD!
-                            self.write_as_big_endian(number)?;
-                        }
-                    }
+                    #[cfg(target_endian = "little")]
+                    unsafe { write_converted(self, value.iter().copied(), |chunk| chunk.convert_current_to_big_endian())?; }
 
                     // else write whole slice
                     #[cfg(target_endian = "big")]
@@ -482,97 +833,1589 @@ pub mod io {
         f64, f32
     }
 
+    #[cfg(feature = "half")]
+    call_single_arg_macro_for_each! {
+        implement_slice_io,
+        f16, bf16
+    }
 
+    // the number of values that are converted at once, using a buffer on the stack
+    pub(crate) const CHUNK_SIZE: usize = 1024;
+
+    // collect the values into a buffer on the stack, and pass each chunk to the closure.
+    // only the used part of the buffer is initialized, so that writing a few values stays cheap.
+    // returns the number of values
+    pub(crate) fn write_chunks<W: ?Sized, T: Copy>(
+        write: &mut W, values: impl IntoIterator<Item = T>,
+        mut write_chunk: impl FnMut(&mut W, &mut [T]) -> Result<()>
+    ) -> Result<usize> {
+        let mut buffer = [std::mem::MaybeUninit::<T>::uninit(); CHUNK_SIZE];
+        let mut values = values.into_iter();
+        let mut count = 0;
+
+        loop {
+            let mut len = 0;
+
+            for (slot, value) in buffer.iter_mut().zip(&mut values) {
+                *slot = std::mem::MaybeUninit::new(value);
+                len += 1;
+            }
 
-    // TODO: SIMD
-    /*impl<R: Read> ReadEndian<[f32]> for R {
-        fn read_from_little_endian_into(&mut self, value: &mut [f32]) -> Result<()> {
-            unsafe { bytes::read_slice(self, value)? };
-            value.convert_little_endian_to_current();
-            Ok(())
-        }
+            if len == 0 { return Ok(count); }
 
-        fn read_from_big_endian_into(&mut self, value: &mut [f32]) -> Result<()> {
-            unsafe { bytes::read_slice(self, value)? };
-            value.convert_big_endian_to_current();
-            Ok(())
+            // the first `len` elements have just been initialized
+            let chunk = unsafe { std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut T, len) };
+            write_chunk(write, chunk)?;
+            count += len;
+
+            if len < CHUNK_SIZE { return Ok(count); }
         }
     }
 
-    impl<W: Write> WriteEndian<[f32]> for W {
-        fn write_as_big_endian(&mut self, value: &[f32]) -> Result<()> {
-            if cfg!(target_endian = "little") {
+    // convert each chunk of `write_chunks` in place, for example to the target byte order, and write it at once.
+    // only used for primitives, see the `bytes` module for safety
+    pub(crate) unsafe fn write_converted<W: Write + ?Sized, T: Copy>(
+        write: &mut W, values: impl IntoIterator<Item = T>, convert: impl Fn(&mut [T])
+    ) -> Result<()> {
+        write_chunks(write, values, |write, chunk| {
+            convert(chunk);
+            write.write_all(bytes::slice_as_bytes(chunk))
+        })?;
+
+        Ok(())
+    }
 
-                // FIX ME this SIMD optimization makes no difference ... why? like, ZERO difference, not even worse
-//                #[cfg(feature = "simd")]
-                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-                unsafe {
-                    if is_x86_feature_detected!("avx2") {
-                        write_bytes_avx(self, value);
-                        return Ok(());
-                    }
-                }
+    /// The result of reading a slice from a stream that may end in the middle of the slice.
+    /// Also contains the error that interrupted reading, if any, together with the progress made before it.
+    #[derive(Debug, Default)]
+    #[must_use]
+    pub struct PartialRead {
+        complete_elements: usize,
+        partial_bytes: [u8; 16],
+        partial_len: usize,
+        error: Option<std::io::Error>,
+    }
 
-                // otherwise (no avx2 available)
-//                for number in value {
-//                    self.write_as_little_endian(number);
-//                }
-//
-//                return Ok(());
-                unimplemented!();
+    impl PartialRead {
 
-                #[target_feature(enable = "avx2")]
-                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-                unsafe fn write_bytes_avx(write: &mut impl Write, slice: &[f32]) -> Result<()> {
-                    #[cfg(target_arch = "x86")] use std::arch::x86 as mm;
-                    #[cfg(target_arch = "x86_64")] use std::arch::x86_64 as mm;
+        /// The number of elements at the start of the slice that were read completely and converted.
+        #[inline]
+        pub fn complete_elements(&self) -> usize {
+            self.complete_elements
+        }
 
-                    let bytes: &[u8] = crate::io::bytes::slice_as_bytes(slice);
-                    let mut chunks = bytes.chunks_exact(32);
+        /// The raw bytes of the element following the complete elements,
+        /// if the stream ended in the middle of it. Empty otherwise.
+        #[inline]
+        pub fn partial_bytes(&self) -> &[u8] {
+            &self.partial_bytes[.. self.partial_len]
+        }
 
-                    let indices = mm::_mm256_set_epi8(
-                        0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,
-                        0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15
-//                        3,2,1,0, 7,6,5,4, 11,10,9,8, 15,14,13,12,
-//                        3,2,1,0, 7,6,5,4, 11,10,9,8, 15,14,13,12
-                    );
+        /// The error that interrupted reading before the slice was filled, if any.
+        /// The end of the stream is not an error.
+        #[inline]
+        pub fn error(&self) -> Option<&std::io::Error> {
+            self.error.as_ref()
+        }
 
-                    for chunk in &mut chunks {
-                        let data = mm::_mm256_loadu_si256(chunk.as_ptr() as _);
-                        let result = mm::_mm256_shuffle_epi8(data, indices);
-                        let mut out = [0_u8; 32];
-                        mm::_mm256_storeu_si256(out.as_mut_ptr() as _, result);
-                        write.write_all(&out)?;
-                    }
+        /// Returns the error that interrupted reading, discarding the progress,
+        /// or the progress if no error occurred.
+        #[inline]
+        pub fn into_result(mut self) -> Result<Self> {
+            match self.error.take() {
+                Some(error) => Err(error),
+                None => Ok(self),
+            }
+        }
+    }
 
-                    let remainder = chunks.remainder();
+    /// A `std::io::Read` input stream which supports reading slices of primitives
+    /// from a stream that may end before the slice is filled, for example a cut-off capture.
+    ///
+    /// Instead of returning an `UnexpectedEof` error, these methods report
+    /// how many elements were completely read and converted, and the bytes of an incomplete last element.
+    /// The elements after the complete elements have unspecified values.
+    /// Other errors are returned inside the `PartialRead`, after converting the elements that were read before the error.
+    ///
+    /// This extension trait is implemented for all `Read` types.
+    pub trait ReadPartial<T> {
 
-                    { // copy remainder into larger slice, with zeroes at the end
-                        let mut last_chunk = [0_u8; 32];
-                        last_chunk[0..remainder.len()].copy_from_slice(remainder);
-                        let data = mm::_mm256_loadu_si256(last_chunk.as_ptr() as _);
-                        let result = mm::_mm256_shuffle_epi8(data, indices);
-                        mm::_mm256_storeu_si256(last_chunk.as_mut_ptr() as _, result);
-                        write.write_all(&last_chunk[0..remainder.len()])?;
-                    }
+        /// Read as many little endian elements as the stream contains, up to the length of the slice.
+        fn read_partial_from_little_endian_into(&mut self, values: &mut [T]) -> PartialRead;
 
-                    Ok(())
+        /// Read as many big endian elements as the stream contains, up to the length of the slice.
+        fn read_partial_from_big_endian_into(&mut self, values: &mut [T]) -> PartialRead;
+    }
+
+    macro_rules! implement_partial_read {
+        ($type: ident) => {
+            impl<R: Read> ReadPartial<$type> for R {
+                fn read_partial_from_little_endian_into(&mut self, values: &mut [$type]) -> PartialRead {
+                    let progress = unsafe { read_partial_slice(self, values) };
+                    values[.. progress.complete_elements].convert_little_endian_to_current();
+                    progress
                 }
-            }
 
-            else {
-                unsafe { bytes::write_slice(self, value)?; }
-                Ok(())
+                fn read_partial_from_big_endian_into(&mut self, values: &mut [$type]) -> PartialRead {
+                    let progress = unsafe { read_partial_slice(self, values) };
+                    values[.. progress.complete_elements].convert_big_endian_to_current();
+                    progress
+                }
             }
-        }
+        };
+    }
 
-        fn write_as_little_endian(&mut self, value: &[f32]) -> Result<()> {
-            for number in value {
-                self.write_as_little_endian(number)?;
-            }
+    call_single_arg_macro_for_each! {
+        implement_partial_read,
+        u8, u16, u32, u64, u128,
+        i8, i16, i32, i64, i128,
+        f64, f32
+    }
 
-            Ok(())
+    #[cfg(feature = "half")]
+    call_single_arg_macro_for_each! {
+        implement_partial_read,
+        f16, bf16
+    }
+
+    // read the raw bytes without converting them, see the `bytes` module for safety
+    unsafe fn read_partial_slice<R: Read, T>(read: &mut R, values: &mut [T]) -> PartialRead {
+        let element_size = std::mem::size_of::<T>();
+        debug_assert!(element_size <= 16, "partial element does not fit into the buffer");
+
+        let bytes = bytes::slice_as_bytes_mut(values);
+        let (available, result) = read_available(read, bytes);
+
+        let complete_elements = available / element_size;
+        let partial = &bytes[complete_elements * element_size .. available];
+
+        let mut partial_bytes = [0_u8; 16];
+        partial_bytes[.. partial.len()].copy_from_slice(partial);
+
+        PartialRead { complete_elements, partial_bytes, partial_len: partial.len(), error: result.err() }
+    }
+
+    /// A `std::io::Read` input stream which supports decoding values lazily using an iterator.
+    ///
+    /// The iterator reads large chunks of values using the fast slice path of `ReadPartial`,
+    /// and yields them one by one. It stops cleanly if the stream ends between two values,
+    /// and yields an `UnexpectedEof` error if the stream ends in the middle of a value.
+    /// If reading fails, the values read before the error are yielded first, followed by the error.
+    ///
+    /// This extension trait is implemented for all `Read` types.
+    pub trait IterEndian: Read {
+
+        /// Iterate over little endian values until the stream ends.
+        /// As whole chunks are read at once, the stream may have advanced
+        /// further than the last yielded value when the iterator is dropped early.
+        #[inline]
+        fn iter_little_endian<T>(&mut self) -> EndianIter<'_, Self, T> where Self: ReadPartial<T>, T: Copy + Default {
+            EndianIter::new(self, None, false)
         }
-    }*/
-}
 
+        /// Iterate over big endian values until the stream ends.
+        /// As whole chunks are read at once, the stream may have advanced
+        /// further than the last yielded value when the iterator is dropped early.
+        #[inline]
+        fn iter_big_endian<T>(&mut self) -> EndianIter<'_, Self, T> where Self: ReadPartial<T>, T: Copy + Default {
+            EndianIter::new(self, None, true)
+        }
+
+        /// Iterate over at most `count` little endian values, like `iter_little_endian().take(count)`,
+        /// but never reads any bytes after the last value.
+        #[inline]
+        fn iter_little_endian_count<T>(&mut self, count: usize) -> EndianIter<'_, Self, T> where Self: ReadPartial<T>, T: Copy + Default {
+            EndianIter::new(self, Some(count), false)
+        }
+
+        /// Iterate over at most `count` big endian values, like `iter_big_endian().take(count)`,
+        /// but never reads any bytes after the last value.
+        #[inline]
+        fn iter_big_endian_count<T>(&mut self, count: usize) -> EndianIter<'_, Self, T> where Self: ReadPartial<T>, T: Copy + Default {
+            EndianIter::new(self, Some(count), true)
+        }
+    }
+
+    impl<R: Read + ?Sized> IterEndian for R {}
+
+    /// An iterator that decodes values from a stream in chunks. Created by the methods of `IterEndian`.
+    #[derive(Debug)]
+    pub struct EndianIter<'r, R: ?Sized, T> {
+        read: &'r mut R,
+        buffer: Vec<T>,
+        position: usize,
+        filled: usize,
+        remaining: Option<usize>,
+        big_endian: bool,
+        ended: bool,
+        error: Option<std::io::Error>,
+    }
+
+    impl<'r, R: ReadPartial<T> + ?Sized, T: Copy + Default> EndianIter<'r, R, T> {
+        fn new(read: &'r mut R, remaining: Option<usize>, big_endian: bool) -> Self {
+            let capacity = remaining.map_or(CHUNK_SIZE, |remaining| remaining.min(CHUNK_SIZE));
+
+            EndianIter {
+                read, big_endian, remaining,
+                buffer: vec![T::default(); capacity],
+                position: 0, filled: 0,
+                ended: false, error: None,
+            }
+        }
+
+        fn refill(&mut self) {
+            let len = self.remaining.map_or(self.buffer.len(), |remaining| remaining.min(self.buffer.len()));
+            let chunk = &mut self.buffer[.. len];
+
+            let progress =
+                if self.big_endian { self.read.read_partial_from_big_endian_into(chunk) }
+                else { self.read.read_partial_from_little_endian_into(chunk) };
+
+            self.position = 0;
+            self.filled = progress.complete_elements();
+            if let Some(remaining) = &mut self.remaining { *remaining -= self.filled; }
+
+            if self.filled < len { self.ended = true; }
+            if self.remaining == Some(0) { self.ended = true; }
+
+            // the values before the error or the truncated value are still yielded first
+            self.error = match progress.into_result() {
+                Err(error) => Some(error),
+
+                Ok(progress) if !progress.partial_bytes().is_empty() => Some(crate::Error::unexpected_end(
+                    std::any::type_name::<T>(), std::mem::size_of::<T>(), progress.partial_bytes().len()
+                ).into()),
+
+                Ok(_) => None,
+            };
+        }
+    }
+
+    impl<'r, R: ReadPartial<T> + ?Sized, T: Copy + Default> Iterator for EndianIter<'r, R, T> {
+        type Item = Result<T>;
+
+        fn next(&mut self) -> Option<Result<T>> {
+            if self.position == self.filled && !self.ended {
+                self.refill();
+            }
+
+            if self.position < self.filled {
+                self.position += 1;
+                return Some(Ok(self.buffer[self.position - 1]));
+            }
+
+            self.error.take().map(Err)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let buffered = self.filled - self.position;
+            let failed = usize::from(self.error.is_some());
+
+            if self.ended { (buffered + failed, Some(buffered + failed)) }
+            else { (buffered, self.remaining.map(|remaining| buffered + remaining)) }
+        }
+    }
+
+    impl<'r, R: ReadPartial<T> + ?Sized, T: Copy + Default> std::iter::FusedIterator for EndianIter<'r, R, T> {}
+
+    /// A `std::io::Write` output stream which supports writing all values of an iterator,
+    /// without collecting them into a `Vec` first.
+    ///
+    /// The values are collected into a buffer on the stack, which is written using the slice path of `WriteEndian`.
+    /// For primitives, this converts and writes up to 1024 values at once, in either byte order.
+    ///
+    /// This extension trait is implemented for all `Write` types.
+    pub trait WriteIterEndian: Write {
+
+        /// Write all values of the iterator as little endian. Returns the number of values written.
+        #[inline]
+        fn write_iter_as_little_endian<T, I>(&mut self, values: I) -> Result<usize>
+            where I: IntoIterator<Item = T>, T: Copy, Self: WriteEndian<[T]>
+        {
+            write_iter(self, values, |write, chunk| write.write_as_little_endian(chunk))
+        }
+
+        /// Write all values of the iterator as big endian. Returns the number of values written.
+        #[inline]
+        fn write_iter_as_big_endian<T, I>(&mut self, values: I) -> Result<usize>
+            where I: IntoIterator<Item = T>, T: Copy, Self: WriteEndian<[T]>
+        {
+            write_iter(self, values, |write, chunk| write.write_as_big_endian(chunk))
+        }
+    }
+
+    impl<W: Write + ?Sized> WriteIterEndian for W {}
+
+    fn write_iter<W: ?Sized, T: Copy>(
+        write: &mut W, values: impl IntoIterator<Item = T>,
+        write_chunk: impl Fn(&mut W, &[T]) -> Result<()>
+    ) -> Result<usize> {
+        write_chunks(write, values, |write, chunk| write_chunk(write, chunk))
+    }
+
+    /// A `std::io::BufRead` input stream which supports decoding values directly from its internal buffer.
+    ///
+    /// The value is decoded from the buffer returned by `fill_buf`, and only the decoded bytes are consumed afterwards,
+    /// which avoids the bookkeeping of `Read::read_exact`. If the buffer does not contain the whole value,
+    /// for example if the value crosses the end of the buffer, these methods use the regular `ReadEndian` implementation instead.
+    /// Works for all types that `ReadEndian` supports, including types that validate their bytes.
+    ///
+    /// This extension trait is implemented for all `BufRead` types.
+    pub trait BufReadEndian: BufRead {
+
+        /// Read into the supplied reference, preferably directly from the internal buffer.
+        #[inline]
+        fn read_buffered_from_little_endian_into<T: ?Sized>(&mut self, value: &mut T) -> Result<()>
+            where Self: ReadEndian<T>, for<'a> &'a [u8]: ReadEndian<T>
+        {
+            read_buffered(self, value, |read, value| read.read_from_little_endian_into(value), |read, value| read.read_from_little_endian_into(value))
+        }
+
+        /// Read into the supplied reference, preferably directly from the internal buffer.
+        #[inline]
+        fn read_buffered_from_big_endian_into<T: ?Sized>(&mut self, value: &mut T) -> Result<()>
+            where Self: ReadEndian<T>, for<'a> &'a [u8]: ReadEndian<T>
+        {
+            read_buffered(self, value, |read, value| read.read_from_big_endian_into(value), |read, value| read.read_from_big_endian_into(value))
+        }
+
+        /// Read a value of the inferred type, preferably directly from the internal buffer.
+        #[inline]
+        fn read_buffered_from_little_endian<T: Default>(&mut self) -> Result<T>
+            where Self: ReadEndian<T>, for<'a> &'a [u8]: ReadEndian<T>
+        {
+            let mut value = T::default();
+            self.read_buffered_from_little_endian_into(&mut value)?;
+            Ok(value)
+        }
+
+        /// Read a value of the inferred type, preferably directly from the internal buffer.
+        #[inline]
+        fn read_buffered_from_big_endian<T: Default>(&mut self) -> Result<T>
+            where Self: ReadEndian<T>, for<'a> &'a [u8]: ReadEndian<T>
+        {
+            let mut value = T::default();
+            self.read_buffered_from_big_endian_into(&mut value)?;
+            Ok(value)
+        }
+
+        /// Decode the next little endian value from the internal buffer, without consuming it.
+        /// Returns `None` if the buffer does not contain the whole value. This happens at the end of the stream,
+        /// but also if the value crosses the end of the internal buffer, and the two cases cannot be told apart here.
+        #[inline]
+        fn peek_from_little_endian<T: Default>(&mut self) -> Result<Option<T>> where for<'a> &'a [u8]: ReadEndian<T> {
+            let mut buffer = self.fill_buf()?;
+            not_buffered_to_none(buffer.read_from_little_endian())
+        }
+
+        /// Decode the next big endian value from the internal buffer, without consuming it.
+        /// Returns `None` if the buffer does not contain the whole value. This happens at the end of the stream,
+        /// but also if the value crosses the end of the internal buffer, and the two cases cannot be told apart here.
+        #[inline]
+        fn peek_from_big_endian<T: Default>(&mut self) -> Result<Option<T>> where for<'a> &'a [u8]: ReadEndian<T> {
+            let mut buffer = self.fill_buf()?;
+            not_buffered_to_none(buffer.read_from_big_endian())
+        }
+    }
+
+    impl<R: BufRead + ?Sized> BufReadEndian for R {}
+
+    // decode from the buffer and consume the decoded bytes, or read from the stream if the buffer does not contain the whole value.
+    // the encoded size is not known up front, as it differs from the size in memory for types like `U24`
+    #[inline]
+    fn read_buffered<R: BufRead + ?Sized, T: ?Sized>(
+        read: &mut R, value: &mut T,
+        from_buffer: impl Fn(&mut &[u8], &mut T) -> Result<()>,
+        from_stream: impl Fn(&mut R, &mut T) -> Result<()>,
+    ) -> Result<()> {
+        let buffer = read.fill_buf()?;
+        let mut remaining = buffer;
+
+        match from_buffer(&mut remaining, value) {
+            Ok(()) => {
+                let consumed = buffer.len() - remaining.len();
+                read.consume(consumed);
+                Ok(())
+            },
+
+            Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => from_stream(read, value),
+            Err(error) => Err(error),
+        }
+    }
+
+    // a value that does not fit into the remaining buffer cannot be peeked
+    #[inline]
+    fn not_buffered_to_none<T>(result: Result<T>) -> Result<Option<T>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// A `std::io::Read` input stream which supports reading values that may be missing at the end of the stream,
+    /// as in record-oriented files that end where the next record would start.
+    ///
+    /// These methods return `None` or `false` only if the stream ends before the first byte of the value.
+    /// If the stream ends anywhere after that, an `UnexpectedEof` error is returned as usual,
+    /// so that a truncated file can be told apart from a complete one.
+    /// The first byte is read using `read`, and the remaining bytes using `read_exact`, so no buffering is required.
+    ///
+    /// This trait is implemented for all `Read` types, for primitives and slices of primitives.
+    /// Use `TryReadRecord` on a `BufRead` stream for other types, like `bool` or `char`.
+    pub trait TryReadEndian<T: ?Sized> {
+
+        /// Read little endian values into the supplied reference, for example a slice.
+        /// Returns `false` if the stream has already ended. Never reads anything into an empty slice.
+        fn try_read_from_little_endian_into(&mut self, value: &mut T) -> Result<bool>;
+
+        /// Read big endian values into the supplied reference, for example a slice.
+        /// Returns `false` if the stream has already ended. Never reads anything into an empty slice.
+        fn try_read_from_big_endian_into(&mut self, value: &mut T) -> Result<bool>;
+
+        /// Read a little endian value of the inferred type, or `None` if the stream has already ended.
+        #[inline]
+        fn try_read_from_little_endian(&mut self) -> Result<Option<T>> where T: Sized + Default {
+            let mut value = T::default();
+            Ok(self.try_read_from_little_endian_into(&mut value)?.then_some(value))
+        }
+
+        /// Read a big endian value of the inferred type, or `None` if the stream has already ended.
+        #[inline]
+        fn try_read_from_big_endian(&mut self) -> Result<Option<T>> where T: Sized + Default {
+            let mut value = T::default();
+            Ok(self.try_read_from_big_endian_into(&mut value)?.then_some(value))
+        }
+    }
+
+    macro_rules! implement_try_read {
+        ($type: ident) => {
+            impl<R: Read> TryReadEndian<$type> for R {
+                fn try_read_from_little_endian_into(&mut self, value: &mut $type) -> Result<bool> {
+                    if !try_read_exact(self, unsafe { bytes::value_as_bytes_mut(value) }, std::any::type_name::<$type>())? { return Ok(false); }
+                    value.convert_little_endian_to_current();
+                    Ok(true)
+                }
+
+                fn try_read_from_big_endian_into(&mut self, value: &mut $type) -> Result<bool> {
+                    if !try_read_exact(self, unsafe { bytes::value_as_bytes_mut(value) }, std::any::type_name::<$type>())? { return Ok(false); }
+                    value.convert_big_endian_to_current();
+                    Ok(true)
+                }
+            }
+
+            impl<R: Read> TryReadEndian<[$type]> for R {
+                fn try_read_from_little_endian_into(&mut self, value: &mut [$type]) -> Result<bool> {
+                    if !try_read_exact(self, unsafe { bytes::slice_as_bytes_mut(value) }, std::any::type_name::<[$type]>())? { return Ok(false); }
+                    value.convert_little_endian_to_current();
+                    Ok(true)
+                }
+
+                fn try_read_from_big_endian_into(&mut self, value: &mut [$type]) -> Result<bool> {
+                    if !try_read_exact(self, unsafe { bytes::slice_as_bytes_mut(value) }, std::any::type_name::<[$type]>())? { return Ok(false); }
+                    value.convert_big_endian_to_current();
+                    Ok(true)
+                }
+            }
+        };
+    }
+
+    call_single_arg_macro_for_each! {
+        implement_try_read,
+        u8, u16, u32, u64, u128,
+        i8, i16, i32, i64, i128,
+        f64, f32
+    }
+
+    #[cfg(feature = "half")]
+    call_single_arg_macro_for_each! {
+        implement_try_read,
+        f16, bf16
+    }
+
+    // read the first byte to check for the end of the stream, then all bytes using `read_exact`,
+    // starting with the byte that was already read, so that errors report the whole value
+    fn try_read_exact<R: Read + ?Sized>(read: &mut R, bytes: &mut [u8], type_name: &'static str) -> Result<bool> {
+        if bytes.is_empty() { return Ok(true); }
+
+        let mut first = [0_u8];
+        let (count, result) = read_available(read, &mut first);
+        result?;
+
+        if count == 0 { return Ok(false); }
+
+        crate::error::read_exact(&mut (&first[..]).chain(read), bytes, type_name)?;
+        Ok(true)
+    }
+
+    /// A `std::io::BufRead` input stream which supports reading records that may be missing at the end of the stream.
+    ///
+    /// Returns `None` only if the stream ends before the first byte of the record, like `TryReadEndian`.
+    /// The end of the stream is detected using `fill_buf`, without consuming anything,
+    /// so that the record can then be read directly from this stream, using any reading method.
+    /// Wrap a plain `Read` stream in a `std::io::BufReader` first.
+    ///
+    /// This extension trait is implemented for all `BufRead` types.
+    pub trait TryReadRecord: BufRead {
+
+        /// Read a whole record consisting of multiple values using the closure,
+        /// or return `None` if the stream has already ended.
+        /// The closure is only called if there is at least one byte left.
+        fn try_read_record<V>(&mut self, read_record: impl FnOnce(&mut Self) -> Result<V>) -> Result<Option<V>> {
+            loop {
+                match self.fill_buf() {
+                    Ok([]) => return Ok(None),
+                    Ok(_) => return read_record(self).map(Some),
+                    Err(error) if error.kind() == std::io::ErrorKind::Interrupted => {},
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+    }
+
+    impl<R: BufRead + ?Sized> TryReadRecord for R {}
+
+    // like `Read::read_exact`, but returns the number of bytes that were read before the stream ended,
+    // which is also returned alongside an error, as those bytes have already been consumed
+    pub(crate) fn read_available<R: Read + ?Sized>(read: &mut R, mut buffer: &mut [u8]) -> (usize, Result<()>) {
+        let requested = buffer.len();
+
+        while !buffer.is_empty() {
+            match read.read(buffer) {
+                Ok(0) => break,
+                Ok(count) => buffer = &mut buffer[count ..],
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => {},
+                Err(error) => return (requested - buffer.len(), Err(error)),
+            }
+        }
+
+        (requested - buffer.len(), Ok(()))
+    }
+
+    /// A `std::io::Read` input stream which supports reading length-delimited sections,
+    /// as found in RIFF, PNG, IFF and MP4 files, without reading past their end.
+    ///
+    /// This extension trait is implemented for all `Read` types.
+    pub trait ReadChunk: Read {
+
+        /// Read a section of the specified number of bytes using the returned `Chunk`,
+        /// which supports all reading methods, including `chunk` for nested sections.
+        #[inline]
+        fn chunk(&mut self, len: u64) -> Chunk<'_, Self> {
+            Chunk { inner: self, remaining: len, finished: false }
+        }
+    }
+
+    impl<R: Read + ?Sized> ReadChunk for R {}
+
+    /// A length-delimited section of a stream, created by `ReadChunk::chunk`.
+    ///
+    /// Behaves as if the stream ended at the end of the chunk,
+    /// so reading past its end returns an `UnexpectedEof` error.
+    /// When dropped, the unread rest of the chunk is skipped, ignoring any errors.
+    /// Call `finish` to skip the rest and handle errors, for example a stream that ends inside the chunk.
+    #[derive(Debug)]
+    pub struct Chunk<'r, R: Read + ?Sized> {
+        inner: &'r mut R,
+        remaining: u64,
+        finished: bool,
+    }
+
+    impl<'r, R: Read + ?Sized> Chunk<'r, R> {
+
+        /// The number of bytes that have not been read from this chunk yet.
+        #[inline]
+        pub fn remaining(&self) -> u64 {
+            self.remaining
+        }
+
+        /// Skip the unread rest of the chunk, so that the original stream continues after the chunk.
+        /// Returns an `UnexpectedEof` error if the original stream ends before the end of the chunk.
+        pub fn finish(mut self) -> Result<()> {
+            self.finished = true;
+            self.skip_rest()
+        }
+
+        // reads through this chunk, so that the remaining bytes are counted even if skipping fails
+        fn skip_rest(&mut self) -> Result<()> {
+            let remaining = self.remaining;
+            ReadPadding::skip_bytes(self, remaining)
+        }
+
+        // the number of bytes of a buffer that may be filled without reading past the end of the chunk
+        #[inline]
+        fn limit(&self, len: usize) -> usize {
+            if len as u64 > self.remaining { self.remaining as usize } else { len }
+        }
+    }
+
+    impl<'r, R: Read + ?Sized> Read for Chunk<'r, R> {
+        fn read(&mut self, buffer: &mut [u8]) -> Result<usize> {
+            let len = self.limit(buffer.len());
+            if len == 0 { return Ok(0); }
+
+            let count = self.inner.read(&mut buffer[.. len])?;
+            self.remaining -= count as u64;
+            Ok(count)
+        }
+    }
+
+    impl<'r, R: BufRead + ?Sized> BufRead for Chunk<'r, R> {
+        fn fill_buf(&mut self) -> Result<&[u8]> {
+            if self.remaining == 0 { return Ok(&[]); }
+
+            let remaining = self.remaining;
+            let buffer = self.inner.fill_buf()?;
+            let len = if buffer.len() as u64 > remaining { remaining as usize } else { buffer.len() };
+            Ok(&buffer[.. len])
+        }
+
+        fn consume(&mut self, amount: usize) {
+            let amount = self.limit(amount);
+            self.inner.consume(amount);
+            self.remaining -= amount as u64;
+        }
+    }
+
+    impl<'r, R: Read + ?Sized> Drop for Chunk<'r, R> {
+        fn drop(&mut self) {
+            // errors cannot be reported here, and leave the original stream inside the chunk
+            if !self.finished {
+                let _ = self.skip_rest();
+            }
+        }
+    }
+
+    /// A `std::io::Read` input stream which supports skipping padding and reserved fields.
+    /// Always reads and discards the skipped bytes, even if the stream supports seeking.
+    /// Use `SeekPadding` to skip by seeking, or a `PositionedReader` to skip to an aligned position.
+    ///
+    /// This extension trait is implemented for all `Read` types.
+    pub trait ReadPadding: Read {
+
+        /// Read and discard the specified number of bytes.
+        /// Returns an `UnexpectedEof` error if the stream ends before all bytes were skipped.
+        fn skip_bytes(&mut self, count: u64) -> Result<()> {
+            let skipped = std::io::copy(&mut self.take(count), &mut std::io::sink())?;
+
+            if skipped != count {
+                return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "stream ended before all bytes were skipped"));
+            }
+
+            Ok(())
+        }
+    }
+
+    /// A `std::io::Write` output stream which supports writing padding and reserved fields.
+    /// Use `PositionedWriter` to pad to an aligned position.
+    ///
+    /// This extension trait is implemented for all `Write` types.
+    pub trait WritePadding: Write {
+
+        /// Write the specified number of zero bytes.
+        fn write_zeros(&mut self, mut count: u64) -> Result<()> {
+            const ZEROS: [u8; 1024] = [0; 1024];
+
+            while count != 0 {
+                let len = count.min(ZEROS.len() as u64);
+                self.write_all(&ZEROS[.. len as usize])?;
+                count -= len;
+            }
+
+            Ok(())
+        }
+    }
+
+    /// A `std::io::Seek` input stream which supports skipping padding and reserved fields by seeking.
+    /// Unlike plain seeking, skipping past the end of the stream is an error.
+    ///
+    /// This extension trait is implemented for all `Read + Seek` types.
+    pub trait SeekPadding: Read + Seek {
+
+        /// Seek past the specified number of bytes.
+        /// Returns an `UnexpectedEof` error if the stream ends before all bytes were skipped,
+        /// in which case the stream is positioned at its end.
+        /// Looks up the length of the stream each time, which a seekable `PositionedReader` only does once.
+        fn skip_bytes_by_seeking(&mut self, count: u64) -> Result<()> {
+            if count == 0 { return Ok(()); }
+
+            let position = self.stream_position()?;
+            let end = self.seek(SeekFrom::End(0))?;
+            let target = position.saturating_add(count);
+            self.seek(SeekFrom::Start(target.min(end.max(position))))?;
+
+            if target > end {
+                return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "stream ended before all bytes were skipped"));
+            }
+
+            Ok(())
+        }
+    }
+
+    impl<R: Read + ?Sized> ReadPadding for R {}
+    impl<R: Read + Seek + ?Sized> SeekPadding for R {}
+    impl<W: Write + ?Sized> WritePadding for W {}
+
+    /// A `std::io::Write` output stream which supports writing values in the mixed-endian layouts of `MixedEndian`,
+    /// as found in PDP-11 data and ARM FPA doubles.
+    ///
+    /// This extension trait is implemented for all `Write` types.
+    pub trait WriteMixedEndian<T: ?Sized> {
+
+        /// Write the value, converting it to word-swapped little endian.
+        fn write_as_word_swapped_little_endian(&mut self, value: &T) -> Result<()>;
+
+        /// Write the value, converting it to word-swapped big endian.
+        fn write_as_word_swapped_big_endian(&mut self, value: &T) -> Result<()>;
+    }
+
+    /// A `std::io::Read` input stream which supports reading values in the mixed-endian layouts of `MixedEndian`,
+    /// as found in PDP-11 data and ARM FPA doubles.
+    ///
+    /// This extension trait is implemented for all `Read` types.
+    pub trait ReadMixedEndian<T: ?Sized> {
+
+        /// Read into the supplied reference, converting from word-swapped little endian.
+        fn read_from_word_swapped_little_endian_into(&mut self, value: &mut T) -> Result<()>;
+
+        /// Read into the supplied reference, converting from word-swapped big endian.
+        fn read_from_word_swapped_big_endian_into(&mut self, value: &mut T) -> Result<()>;
+
+        /// Read a value of the inferred type, converting from word-swapped little endian.
+        #[inline]
+        fn read_from_word_swapped_little_endian(&mut self) -> Result<T> where T: Sized + Default {
+            let mut value = T::default();
+            self.read_from_word_swapped_little_endian_into(&mut value)?;
+            Ok(value)
+        }
+
+        /// Read a value of the inferred type, converting from word-swapped big endian.
+        #[inline]
+        fn read_from_word_swapped_big_endian(&mut self) -> Result<T> where T: Sized + Default {
+            let mut value = T::default();
+            self.read_from_word_swapped_big_endian_into(&mut value)?;
+            Ok(value)
+        }
+    }
+
+    // the word-swapped layouts are the little or big endian layouts of the value with swapped halves
+    macro_rules! implement_mixed_endian_io {
+        ($( $type: ident ),* ) => {
+            $(
+                impl<W: Write> WriteMixedEndian<$type> for W {
+                    #[inline]
+                    fn write_as_word_swapped_little_endian(&mut self, value: &$type) -> Result<()> {
+                        let mut value = *value;
+                        value.swap_words();
+                        self.write_as_little_endian(&value)
+                    }
+
+                    #[inline]
+                    fn write_as_word_swapped_big_endian(&mut self, value: &$type) -> Result<()> {
+                        let mut value = *value;
+                        value.swap_words();
+                        self.write_as_big_endian(&value)
+                    }
+                }
+
+                impl<R: Read> ReadMixedEndian<$type> for R {
+                    #[inline]
+                    fn read_from_word_swapped_little_endian_into(&mut self, value: &mut $type) -> Result<()> {
+                        self.read_from_little_endian_into(value)?;
+                        value.swap_words();
+                        Ok(())
+                    }
+
+                    #[inline]
+                    fn read_from_word_swapped_big_endian_into(&mut self, value: &mut $type) -> Result<()> {
+                        self.read_from_big_endian_into(value)?;
+                        value.swap_words();
+                        Ok(())
+                    }
+                }
+
+                impl<W: Write> WriteMixedEndian<[$type]> for W {
+                    fn write_as_word_swapped_little_endian(&mut self, value: &[$type]) -> Result<()> {
+                        // swap the words of a copy, and convert it to the byte order in the same pass
+                        unsafe { write_converted(self, value.iter().copied(), |chunk| { chunk.swap_words(); chunk.convert_current_to_little_endian(); }) }
+                    }
+
+                    fn write_as_word_swapped_big_endian(&mut self, value: &[$type]) -> Result<()> {
+                        unsafe { write_converted(self, value.iter().copied(), |chunk| { chunk.swap_words(); chunk.convert_current_to_big_endian(); }) }
+                    }
+                }
+
+                impl<R: Read> ReadMixedEndian<[$type]> for R {
+                    fn read_from_word_swapped_little_endian_into(&mut self, value: &mut [$type]) -> Result<()> {
+                        self.read_from_little_endian_into(value)?;
+                        value.swap_words();
+                        Ok(())
+                    }
+
+                    fn read_from_word_swapped_big_endian_into(&mut self, value: &mut [$type]) -> Result<()> {
+                        self.read_from_big_endian_into(value)?;
+                        value.swap_words();
+                        Ok(())
+                    }
+                }
+            )*
+        };
+    }
+
+    implement_mixed_endian_io!(u32, i32, f32, f64);
+
+    /// A `std::io::Read` input stream which supports reading tightly packed integers of any bit width,
+    /// as used by scientific and image formats for 10, 12 or 14 bit samples.
+    ///
+    /// The values are packed into a continuous stream of bits, without any padding between them.
+    /// Little endian packing starts with the least significant bits of the first byte,
+    /// for example the 12-bit values `0xABC, 0xDEF` are packed as `[0xBC, 0xFA, 0xDE]`.
+    /// Big endian packing starts with the most significant bits of the first byte,
+    /// so the same values are packed as `[0xAB, 0xCD, 0xEF]`.
+    /// The unused bits of the last byte are ignored.
+    ///
+    /// Returns an `InvalidInput` error if the bit count is zero,
+    /// exceeds the number of bits in the element type, or exceeds 64.
+    ///
+    /// This extension trait is implemented for all `Read` types.
+    pub trait ReadPacked: Read {
+
+        /// Fill the slice with little endian packed integers of the specified bit count.
+        /// Consumes `ceil(values.len() * bits / 8)` bytes.
+        fn read_packed_from_little_endian_into<T: Unsigned>(&mut self, bits: u32, values: &mut [T]) -> Result<()> {
+            read_packed(self, bits, values, false)
+        }
+
+        /// Fill the slice with big endian packed integers of the specified bit count.
+        /// Consumes `ceil(values.len() * bits / 8)` bytes.
+        fn read_packed_from_big_endian_into<T: Unsigned>(&mut self, bits: u32, values: &mut [T]) -> Result<()> {
+            read_packed(self, bits, values, true)
+        }
+    }
+
+    /// A `std::io::Write` output stream which supports writing tightly packed integers of any bit width.
+    /// See `ReadPacked` for the layout. The unused bits of the last byte are filled with zeroes.
+    ///
+    /// Returns an `InvalidInput` error if the bit count is zero,
+    /// exceeds the number of bits in the element type, or exceeds 64,
+    /// or if a value does not fit into the bit count.
+    /// In that case, the preceding values may already have been written.
+    ///
+    /// This extension trait is implemented for all `Write` types.
+    pub trait WritePacked: Write {
+
+        /// Write the values as little endian packed integers of the specified bit count.
+        fn write_packed_as_little_endian<T: Unsigned>(&mut self, bits: u32, values: &[T]) -> Result<()> {
+            write_packed(self, bits, values, false)
+        }
+
+        /// Write the values as big endian packed integers of the specified bit count.
+        fn write_packed_as_big_endian<T: Unsigned>(&mut self, bits: u32, values: &[T]) -> Result<()> {
+            write_packed(self, bits, values, true)
+        }
+    }
+
+    impl<R: Read + ?Sized> ReadPacked for R {}
+    impl<W: Write + ?Sized> WritePacked for W {}
+
+    // packed values are converted in chunks of `CHUNK_SIZE`,
+    // which is a multiple of eight, so that each chunk starts at a byte boundary
+
+    fn check_packed_bits<T: Unsigned>(bits: u32) -> Result<()> {
+        if bits == 0 || bits > T::BITS || bits > 64 {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "unsupported bit count for packed integers"));
+        }
+
+        Ok(())
+    }
+
+    fn read_packed<R: Read + ?Sized, T: Unsigned>(read: &mut R, bits: u32, values: &mut [T], big_endian: bool) -> Result<()> {
+        check_packed_bits::<T>(bits)?;
+        let mut buffer = [0_u8; CHUNK_SIZE * 8];
+
+        for chunk in values.chunks_mut(CHUNK_SIZE) {
+            let bytes = &mut buffer[.. (chunk.len() * bits as usize).div_ceil(8)];
+            crate::error::read_exact(read, bytes, std::any::type_name::<[T]>())?;
+
+            // fast paths for the most common bit counts, processing whole groups of bytes at once
+            let unpacked = match (bits, big_endian) {
+                (12, true) => unpack_groups::<T, 2, 3>(bytes, chunk, |b| [
+                    u32::from(b[0]) << 4 | u32::from(b[1]) >> 4,
+                    u32::from(b[1] & 0x0F) << 8 | u32::from(b[2]),
+                ]),
+
+                (12, false) => unpack_groups::<T, 2, 3>(bytes, chunk, |b| [
+                    u32::from(b[0]) | u32::from(b[1] & 0x0F) << 8,
+                    u32::from(b[1]) >> 4 | u32::from(b[2]) << 4,
+                ]),
+
+                (10, true) => unpack_groups::<T, 4, 5>(bytes, chunk, |b| [
+                    u32::from(b[0]) << 2 | u32::from(b[1]) >> 6,
+                    u32::from(b[1] & 0x3F) << 4 | u32::from(b[2]) >> 4,
+                    u32::from(b[2] & 0x0F) << 6 | u32::from(b[3]) >> 2,
+                    u32::from(b[3] & 0x03) << 8 | u32::from(b[4]),
+                ]),
+
+                (10, false) => unpack_groups::<T, 4, 5>(bytes, chunk, |b| [
+                    u32::from(b[0]) | u32::from(b[1] & 0x03) << 8,
+                    u32::from(b[1]) >> 2 | u32::from(b[2] & 0x0F) << 6,
+                    u32::from(b[2]) >> 4 | u32::from(b[3] & 0x3F) << 4,
+                    u32::from(b[3]) >> 6 | u32::from(b[4]) << 2,
+                ]),
+
+                _ => 0,
+            };
+
+            // the remaining values start at a byte boundary
+            let (bytes, chunk) = (&bytes[unpacked * bits as usize / 8 ..], &mut chunk[unpacked ..]);
+            let mask = u128::MAX >> (128 - bits);
+            let mut bytes = bytes.iter();
+            let mut accumulator = 0_u128;
+            let mut accumulated_bits = 0;
+
+            for value in chunk {
+                while accumulated_bits < bits {
+                    let byte = u128::from(*bytes.next().expect("packed byte count mismatch"));
+
+                    if big_endian { accumulator = accumulator << 8 | byte; }
+                    else { accumulator |= byte << accumulated_bits; }
+
+                    accumulated_bits += 8;
+                }
+
+                accumulated_bits -= bits;
+
+                if big_endian {
+                    *value = T::from_u128_truncated((accumulator >> accumulated_bits) & mask);
+                    accumulator &= !(u128::MAX << accumulated_bits);
+                }
+                else {
+                    *value = T::from_u128_truncated(accumulator & mask);
+                    accumulator >>= bits;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // unpack as many complete groups of bytes as possible, returning the number of values
+    #[inline]
+    fn unpack_groups<T: Unsigned, const VALUES: usize, const BYTES: usize>(
+        bytes: &[u8], values: &mut [T], unpack: impl Fn(&[u8]) -> [u32; VALUES]
+    ) -> usize
+    {
+        let mut count = 0;
+
+        for (values, bytes) in values.chunks_exact_mut(VALUES).zip(bytes.chunks_exact(BYTES)) {
+            for (value, unpacked) in values.iter_mut().zip(unpack(bytes)) {
+                *value = T::from_u128_truncated(u128::from(unpacked));
+            }
+
+            count += VALUES;
+        }
+
+        count
+    }
+
+    fn write_packed<W: Write + ?Sized, T: Unsigned>(write: &mut W, bits: u32, values: &[T], big_endian: bool) -> Result<()> {
+        check_packed_bits::<T>(bits)?;
+        let mut buffer = [0_u8; CHUNK_SIZE * 8];
+
+        for chunk in values.chunks(CHUNK_SIZE) {
+            if chunk.iter().any(|value| value.to_u128() >> bits != 0) {
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "value does not fit into the packed bit count"));
+            }
+
+            let bytes = &mut buffer[.. (chunk.len() * bits as usize).div_ceil(8)];
+
+            // fast paths for the most common bit counts, processing whole groups of values at once
+            let packed = match (bits, big_endian) {
+                (12, true) => pack_groups::<T, 2, 3>(chunk, bytes, |v| [
+                    (v[0] >> 4) as u8, ((v[0] << 4) | (v[1] >> 8)) as u8, v[1] as u8,
+                ]),
+
+                (12, false) => pack_groups::<T, 2, 3>(chunk, bytes, |v| [
+                    v[0] as u8, ((v[0] >> 8) | (v[1] << 4)) as u8, (v[1] >> 4) as u8,
+                ]),
+
+                (10, true) => pack_groups::<T, 4, 5>(chunk, bytes, |v| [
+                    (v[0] >> 2) as u8, ((v[0] << 6) | (v[1] >> 4)) as u8, ((v[1] << 4) | (v[2] >> 6)) as u8,
+                    ((v[2] << 2) | (v[3] >> 8)) as u8, v[3] as u8,
+                ]),
+
+                (10, false) => pack_groups::<T, 4, 5>(chunk, bytes, |v| [
+                    v[0] as u8, ((v[0] >> 8) | (v[1] << 2)) as u8, ((v[1] >> 6) | (v[2] << 4)) as u8,
+                    ((v[2] >> 4) | (v[3] << 6)) as u8, (v[3] >> 2) as u8,
+                ]),
+
+                _ => 0,
+            };
+
+            // the remaining values start at a byte boundary
+            let (remaining_bytes, chunk) = (&mut bytes[packed * bits as usize / 8 ..], &chunk[packed ..]);
+            let mut remaining_bytes = remaining_bytes.iter_mut();
+            let mut accumulator = 0_u128;
+            let mut accumulated_bits = 0;
+
+            for value in chunk {
+                let value = value.to_u128();
+
+                if big_endian { accumulator = accumulator << bits | value; }
+                else { accumulator |= value << accumulated_bits; }
+
+                accumulated_bits += bits;
+
+                while accumulated_bits >= 8 {
+                    accumulated_bits -= 8;
+                    let byte = remaining_bytes.next().expect("packed byte count mismatch");
+
+                    if big_endian {
+                        *byte = (accumulator >> accumulated_bits) as u8;
+                        accumulator &= !(u128::MAX << accumulated_bits);
+                    }
+                    else {
+                        *byte = accumulator as u8;
+                        accumulator >>= 8;
+                    }
+                }
+            }
+
+            // pad the last byte with zeroes
+            if accumulated_bits != 0 {
+                let byte = remaining_bytes.next().expect("packed byte count mismatch");
+                *byte = if big_endian { (accumulator << (8 - accumulated_bits)) as u8 } else { accumulator as u8 };
+            }
+
+            write.write_all(bytes)?;
+        }
+
+        Ok(())
+    }
+
+    // pack as many complete groups of values as possible, returning the number of values
+    #[inline]
+    fn pack_groups<T: Unsigned, const VALUES: usize, const BYTES: usize>(
+        values: &[T], bytes: &mut [u8], pack: impl Fn([u32; VALUES]) -> [u8; BYTES]
+    ) -> usize
+    {
+        let mut count = 0;
+
+        for (values, bytes) in values.chunks_exact(VALUES).zip(bytes.chunks_exact_mut(BYTES)) {
+            let mut group = [0_u32; VALUES];
+            for (unpacked, value) in group.iter_mut().zip(values) {
+                *unpacked = value.to_u128() as u32;
+            }
+
+            bytes.copy_from_slice(&pack(group));
+            count += VALUES;
+        }
+
+        count
+    }
+
+    /// A `std::io::Read` input stream which supports reading half precision floats
+    /// directly into a slice of `f32`, widening each value while decoding.
+    ///
+    /// This extension trait is implemented for all `Read` types.
+    /// Only available with the `half` feature.
+    #[cfg(feature = "half")]
+    pub trait ReadHalfFloats {
+
+        /// Read little endian `f16` values and convert them to `f32`. Consumes two bytes per element.
+        fn read_f16_from_little_endian_into(&mut self, values: &mut [f32]) -> Result<()>;
+
+        /// Read big endian `f16` values and convert them to `f32`. Consumes two bytes per element.
+        fn read_f16_from_big_endian_into(&mut self, values: &mut [f32]) -> Result<()>;
+
+        /// Read little endian `bf16` values and convert them to `f32`. Consumes two bytes per element.
+        fn read_bf16_from_little_endian_into(&mut self, values: &mut [f32]) -> Result<()>;
+
+        /// Read big endian `bf16` values and convert them to `f32`. Consumes two bytes per element.
+        fn read_bf16_from_big_endian_into(&mut self, values: &mut [f32]) -> Result<()>;
+    }
+
+    #[cfg(feature = "half")]
+    impl<R: Read> ReadHalfFloats for R {
+        fn read_f16_from_little_endian_into(&mut self, values: &mut [f32]) -> Result<()> {
+            read_half_floats(self, values, |read, half: &mut [f16]| read.read_from_little_endian_into(half))
+        }
+
+        fn read_f16_from_big_endian_into(&mut self, values: &mut [f32]) -> Result<()> {
+            read_half_floats(self, values, |read, half: &mut [f16]| read.read_from_big_endian_into(half))
+        }
+
+        fn read_bf16_from_little_endian_into(&mut self, values: &mut [f32]) -> Result<()> {
+            read_half_floats(self, values, |read, half: &mut [bf16]| read.read_from_little_endian_into(half))
+        }
+
+        fn read_bf16_from_big_endian_into(&mut self, values: &mut [f32]) -> Result<()> {
+            read_half_floats(self, values, |read, half: &mut [bf16]| read.read_from_big_endian_into(half))
+        }
+    }
+
+    // read chunks into a buffer on the stack using the fast slice path, then widen them all at once
+    #[cfg(feature = "half")]
+    fn read_half_floats<R: Read, H>(
+        read: &mut R, values: &mut [f32],
+        read_chunk: impl Fn(&mut R, &mut [H]) -> Result<()>
+    ) -> Result<()>
+        where H: Copy + Default, [H]: half::slice::HalfFloatSliceExt
+    {
+        use half::slice::HalfFloatSliceExt;
+        let mut buffer = [H::default(); CHUNK_SIZE];
+
+        for chunk in values.chunks_mut(CHUNK_SIZE) {
+            let half = &mut buffer[.. chunk.len()];
+            read_chunk(read, half)?;
+            half.convert_to_f32_slice(chunk);
+        }
+
+        Ok(())
+    }
+
+
+    // implement reading and writing for values that are stored as a primitive,
+    // but where not every primitive value is a valid value.
+    // writing is infallible, reading returns an `InvalidData` error for invalid values.
+    macro_rules! implement_checked_primitive_io {
+        ($type: ty, $primitive: ident, $to_primitive: expr, $from_primitive: expr, $message: expr) => {
+            impl<W: Write> WriteEndian<$type> for W {
+                #[inline]
+                fn write_as_little_endian(&mut self, value: &$type) -> Result<()> {
+                    let primitive: $primitive = $to_primitive(*value);
+                    self.write_as_little_endian(&primitive)
+                }
+
+                #[inline]
+                fn write_as_big_endian(&mut self, value: &$type) -> Result<()> {
+                    let primitive: $primitive = $to_primitive(*value);
+                    self.write_as_big_endian(&primitive)
+                }
+            }
+
+            impl<R: Read> ReadEndian<$type> for R {
+                #[inline]
+                fn read_from_little_endian_into(&mut self, value: &mut $type) -> Result<()> {
+                    let primitive: $primitive = self.read_from_little_endian().map_err(for_type::<$type>)?;
+                    *value = $from_primitive(primitive).ok_or_else(|| invalid_data($message))?;
+                    Ok(())
+                }
+
+                #[inline]
+                fn read_from_big_endian_into(&mut self, value: &mut $type) -> Result<()> {
+                    let primitive: $primitive = self.read_from_big_endian().map_err(for_type::<$type>)?;
+                    *value = $from_primitive(primitive).ok_or_else(|| invalid_data($message))?;
+                    Ok(())
+                }
+            }
+        };
+    }
+
+    // non-zero integers do not implement `Default`, so they can only be read using `read_from_*_endian_into`
+    macro_rules! implement_non_zero_io {
+        ($( $type: ident : $primitive: ident ),* ) => {
+            $(
+                implement_checked_primitive_io!(
+                    std::num::$type, $primitive,
+                    std::num::$type::get, std::num::$type::new,
+                    concat!("invalid ", stringify!($type), " value: zero")
+                );
+            )*
+        };
+    }
+
+    implement_checked_primitive_io!(
+        bool, u8, u8::from,
+        |byte: u8| match byte { 0 => Some(false), 1 => Some(true), _ => None },
+        "invalid bool value: neither zero nor one"
+    );
+
+    implement_checked_primitive_io!(
+        char, u32, u32::from, char::from_u32,
+        "invalid char value: surrogate or out of range"
+    );
+
+    implement_non_zero_io! {
+        NonZeroU8: u8, NonZeroU16: u16, NonZeroU32: u32, NonZeroU64: u64, NonZeroU128: u128,
+        NonZeroI8: i8, NonZeroI16: i16, NonZeroI32: i32, NonZeroI64: i64, NonZeroI128: i128
+    }
+
+    // implement reading and writing for wrappers with the same memory layout as their inner value,
+    // which allows slices of wrappers to use the fast path of the inner slice
+    macro_rules! implement_transparent_wrapper_io {
+        ($wrapper: ident, $( $type: ident ),* ) => {
+            $(
+                impl<W: Write> WriteEndian<std::num::$wrapper<$type>> for W {
+                    #[inline]
+                    fn write_as_little_endian(&mut self, value: &std::num::$wrapper<$type>) -> Result<()> {
+                        self.write_as_little_endian(&value.0)
+                    }
+
+                    #[inline]
+                    fn write_as_big_endian(&mut self, value: &std::num::$wrapper<$type>) -> Result<()> {
+                        self.write_as_big_endian(&value.0)
+                    }
+                }
+
+                impl<R: Read> ReadEndian<std::num::$wrapper<$type>> for R {
+                    #[inline]
+                    fn read_from_little_endian_into(&mut self, value: &mut std::num::$wrapper<$type>) -> Result<()> {
+                        self.read_from_little_endian_into(&mut value.0).map_err(for_type::<std::num::$wrapper<$type>>)
+                    }
+
+                    #[inline]
+                    fn read_from_big_endian_into(&mut self, value: &mut std::num::$wrapper<$type>) -> Result<()> {
+                        self.read_from_big_endian_into(&mut value.0).map_err(for_type::<std::num::$wrapper<$type>>)
+                    }
+                }
+
+                // safe because the wrapper is `repr(transparent)`
+                impl<W: Write> WriteEndian<[std::num::$wrapper<$type>]> for W {
+                    fn write_as_little_endian(&mut self, value: &[std::num::$wrapper<$type>]) -> Result<()> {
+                        self.write_as_little_endian(unsafe { bytes::cast_slice::<_, $type>(value) })
+                    }
+
+                    fn write_as_big_endian(&mut self, value: &[std::num::$wrapper<$type>]) -> Result<()> {
+                        self.write_as_big_endian(unsafe { bytes::cast_slice::<_, $type>(value) })
+                    }
+                }
+
+                impl<R: Read> ReadEndian<[std::num::$wrapper<$type>]> for R {
+                    fn read_from_little_endian_into(&mut self, value: &mut [std::num::$wrapper<$type>]) -> Result<()> {
+                        self.read_from_little_endian_into(unsafe { bytes::cast_slice_mut::<_, $type>(value) })
+                            .map_err(for_type::<[std::num::$wrapper<$type>]>)
+                    }
+
+                    fn read_from_big_endian_into(&mut self, value: &mut [std::num::$wrapper<$type>]) -> Result<()> {
+                        self.read_from_big_endian_into(unsafe { bytes::cast_slice_mut::<_, $type>(value) })
+                            .map_err(for_type::<[std::num::$wrapper<$type>]>)
+                    }
+                }
+            )*
+        };
+    }
+
+    implement_transparent_wrapper_io! {
+        Wrapping,
+        u8, u16, u32, u64, u128,
+        i8, i16, i32, i64, i128
+    }
+
+    implement_transparent_wrapper_io! {
+        Saturating,
+        u8, u16, u32, u64, u128,
+        i8, i16, i32, i64, i128
+    }
+
+    // implement reading and writing for optional non-zero integers, where `None` is stored as zero.
+    // slices use the fast path of the primitive slice,
+    // as `Option<NonZero>` is guaranteed to have the same layout as its primitive
+    macro_rules! implement_optional_non_zero_io {
+        ($( $type: ident : $primitive: ident ),* ) => {
+            $(
+                impl<W: Write> WriteEndian<Option<std::num::$type>> for W {
+                    #[inline]
+                    fn write_as_little_endian(&mut self, value: &Option<std::num::$type>) -> Result<()> {
+                        self.write_as_little_endian(&value.map_or(0, std::num::$type::get))
+                    }
+
+                    #[inline]
+                    fn write_as_big_endian(&mut self, value: &Option<std::num::$type>) -> Result<()> {
+                        self.write_as_big_endian(&value.map_or(0, std::num::$type::get))
+                    }
+                }
+
+                impl<R: Read> ReadEndian<Option<std::num::$type>> for R {
+                    #[inline]
+                    fn read_from_little_endian_into(&mut self, value: &mut Option<std::num::$type>) -> Result<()> {
+                        *value = std::num::$type::new(self.read_from_little_endian().map_err(for_type::<Option<std::num::$type>>)?);
+                        Ok(())
+                    }
+
+                    #[inline]
+                    fn read_from_big_endian_into(&mut self, value: &mut Option<std::num::$type>) -> Result<()> {
+                        *value = std::num::$type::new(self.read_from_big_endian().map_err(for_type::<Option<std::num::$type>>)?);
+                        Ok(())
+                    }
+                }
+
+                impl<W: Write> WriteEndian<[Option<std::num::$type>]> for W {
+                    fn write_as_little_endian(&mut self, value: &[Option<std::num::$type>]) -> Result<()> {
+                        self.write_as_little_endian(unsafe { bytes::cast_slice::<_, $primitive>(value) })
+                    }
+
+                    fn write_as_big_endian(&mut self, value: &[Option<std::num::$type>]) -> Result<()> {
+                        self.write_as_big_endian(unsafe { bytes::cast_slice::<_, $primitive>(value) })
+                    }
+                }
+
+                impl<R: Read> ReadEndian<[Option<std::num::$type>]> for R {
+                    fn read_from_little_endian_into(&mut self, value: &mut [Option<std::num::$type>]) -> Result<()> {
+                        self.read_from_little_endian_into(unsafe { bytes::cast_slice_mut::<_, $primitive>(value) })
+                            .map_err(for_type::<[Option<std::num::$type>]>)
+                    }
+
+                    fn read_from_big_endian_into(&mut self, value: &mut [Option<std::num::$type>]) -> Result<()> {
+                        self.read_from_big_endian_into(unsafe { bytes::cast_slice_mut::<_, $primitive>(value) })
+                            .map_err(for_type::<[Option<std::num::$type>]>)
+                    }
+                }
+            )*
+        };
+    }
+
+    implement_optional_non_zero_io! {
+        NonZeroU8: u8, NonZeroU16: u16, NonZeroU32: u32, NonZeroU64: u64, NonZeroU128: u128,
+        NonZeroI8: i8, NonZeroI16: i16, NonZeroI32: i32, NonZeroI64: i64, NonZeroI128: i128
+    }
+
+    // ip addresses are always stored in their canonical network octet order,
+    // regardless of the requested byte order, as they are not numbers
+    macro_rules! implement_ip_address_io {
+        ($type: ident, $octets: expr) => {
+            impl<W: Write> WriteEndian<std::net::$type> for W {
+                #[inline]
+                fn write_as_little_endian(&mut self, value: &std::net::$type) -> Result<()> {
+                    self.write_all(&value.octets())
+                }
+
+                #[inline]
+                fn write_as_big_endian(&mut self, value: &std::net::$type) -> Result<()> {
+                    self.write_all(&value.octets())
+                }
+            }
+
+            impl<R: Read> ReadEndian<std::net::$type> for R {
+                #[inline]
+                fn read_from_little_endian_into(&mut self, value: &mut std::net::$type) -> Result<()> {
+                    let mut octets = [0_u8; $octets];
+                    crate::error::read_exact(self, &mut octets, std::any::type_name::<std::net::$type>())?;
+                    *value = std::net::$type::from(octets);
+                    Ok(())
+                }
+
+                #[inline]
+                fn read_from_big_endian_into(&mut self, value: &mut std::net::$type) -> Result<()> {
+                    self.read_from_little_endian_into(value)
+                }
+            }
+        };
+    }
+
+    implement_ip_address_io!(Ipv4Addr, 4);
+    implement_ip_address_io!(Ipv6Addr, 16);
+
+    // socket addresses are stored as the address octets,
+    // followed by the numeric fields in the requested byte order
+    impl<W: Write> WriteEndian<std::net::SocketAddrV4> for W {
+        fn write_as_little_endian(&mut self, value: &std::net::SocketAddrV4) -> Result<()> {
+            self.write_as_little_endian(value.ip())?;
+            self.write_as_little_endian(&value.port())
+        }
+
+        fn write_as_big_endian(&mut self, value: &std::net::SocketAddrV4) -> Result<()> {
+            self.write_as_big_endian(value.ip())?;
+            self.write_as_big_endian(&value.port())
+        }
+    }
+
+    impl<R: Read> ReadEndian<std::net::SocketAddrV4> for R {
+        fn read_from_little_endian_into(&mut self, value: &mut std::net::SocketAddrV4) -> Result<()> {
+            let mut ip = std::net::Ipv4Addr::UNSPECIFIED;
+            self.read_from_little_endian_into(&mut ip)?;
+            *value = std::net::SocketAddrV4::new(ip, self.read_from_little_endian()?);
+            Ok(())
+        }
+
+        fn read_from_big_endian_into(&mut self, value: &mut std::net::SocketAddrV4) -> Result<()> {
+            let mut ip = std::net::Ipv4Addr::UNSPECIFIED;
+            self.read_from_big_endian_into(&mut ip)?;
+            *value = std::net::SocketAddrV4::new(ip, self.read_from_big_endian()?);
+            Ok(())
+        }
+    }
+
+    impl<W: Write> WriteEndian<std::net::SocketAddrV6> for W {
+        fn write_as_little_endian(&mut self, value: &std::net::SocketAddrV6) -> Result<()> {
+            self.write_as_little_endian(value.ip())?;
+            self.write_as_little_endian(&value.port())?;
+            self.write_as_little_endian(&value.flowinfo())?;
+            self.write_as_little_endian(&value.scope_id())
+        }
+
+        fn write_as_big_endian(&mut self, value: &std::net::SocketAddrV6) -> Result<()> {
+            self.write_as_big_endian(value.ip())?;
+            self.write_as_big_endian(&value.port())?;
+            self.write_as_big_endian(&value.flowinfo())?;
+            self.write_as_big_endian(&value.scope_id())
+        }
+    }
+
+    impl<R: Read> ReadEndian<std::net::SocketAddrV6> for R {
+        fn read_from_little_endian_into(&mut self, value: &mut std::net::SocketAddrV6) -> Result<()> {
+            let mut ip = std::net::Ipv6Addr::UNSPECIFIED;
+            self.read_from_little_endian_into(&mut ip)?;
+
+            let port = self.read_from_little_endian()?;
+            let flow_info = self.read_from_little_endian()?;
+            let scope_id = self.read_from_little_endian()?;
+            *value = std::net::SocketAddrV6::new(ip, port, flow_info, scope_id);
+            Ok(())
+        }
+
+        fn read_from_big_endian_into(&mut self, value: &mut std::net::SocketAddrV6) -> Result<()> {
+            let mut ip = std::net::Ipv6Addr::UNSPECIFIED;
+            self.read_from_big_endian_into(&mut ip)?;
+
+            let port = self.read_from_big_endian()?;
+            let flow_info = self.read_from_big_endian()?;
+            let scope_id = self.read_from_big_endian()?;
+            *value = std::net::SocketAddrV6::new(ip, port, flow_info, scope_id);
+            Ok(())
+        }
+    }
+
+    /// Create the error that is returned when the bytes in a stream do not represent a valid value.
+    #[inline]
+    pub(crate) fn invalid_data(message: &'static str) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+    }
+
+
+
+    // TODO: SIMD
+    /*impl<R: Read> ReadEndian<[f32]> for R {
+        fn read_from_little_endian_into(&mut self, value: &mut [f32]) -> Result<()> {
+            unsafe { bytes::read_slice(self, value)? };
+            value.convert_little_endian_to_current();
+            Ok(())
+        }
+
+        fn read_from_big_endian_into(&mut self, value: &mut [f32]) -> Result<()> {
+            unsafe { bytes::read_slice(self, value)? };
+            value.convert_big_endian_to_current();
+            Ok(())
+        }
+    }
+
+    impl<W: Write> WriteEndian<[f32]> for W {
+        fn write_as_big_endian(&mut self, value: &[f32]) -> Result<()> {
+            if cfg!(target_endian = "little") {
+
+                // FIX ME this SIMD optimization makes no difference ... why? like, ZERO difference, not even worse
+//                #[cfg(feature = "simd")]
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                unsafe {
+                    if is_x86_feature_detected!("avx2") {
+                        write_bytes_avx(self, value);
+                        return Ok(());
+                    }
+                }
+
+                // otherwise (no avx2 available)
+//                for number in value {
+//                    self.write_as_little_endian(number);
+//                }
+//
+//                return Ok(());
+                unimplemented!();
+
+                #[target_feature(enable = "avx2")]
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                unsafe fn write_bytes_avx(write: &mut impl Write, slice: &[f32]) -> Result<()> {
+                    #[cfg(target_arch = "x86")] use std::arch::x86 as mm;
+                    #[cfg(target_arch = "x86_64")] use std::arch::x86_64 as mm;
+
+                    let bytes: &[u8] = crate::io::bytes::slice_as_bytes(slice);
+                    let mut chunks = bytes.chunks_exact(32);
+
+                    let indices = mm::_mm256_set_epi8(
+                        0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,
+                        0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15
+//                        3,2,1,0, 7,6,5,4, 11,10,9,8, 15,14,13,12,
+//                        3,2,1,0, 7,6,5,4, 11,10,9,8, 15,14,13,12
+                    );
+
+                    for chunk in &mut chunks {
+                        let data = mm::_mm256_loadu_si256(chunk.as_ptr() as _);
+                        let result = mm::_mm256_shuffle_epi8(data, indices);
+                        let mut out = [0_u8; 32];
+                        mm::_mm256_storeu_si256(out.as_mut_ptr() as _, result);
+                        write.write_all(&out)?;
+                    }
+
+                    let remainder = chunks.remainder();
+
+                    { // copy remainder into larger slice, with zeroes at the end
+                        let mut last_chunk = [0_u8; 32];
+                        last_chunk[0..remainder.len()].copy_from_slice(remainder);
+                        let data = mm::_mm256_loadu_si256(last_chunk.as_ptr() as _);
+                        let result = mm::_mm256_shuffle_epi8(data, indices);
+                        mm::_mm256_storeu_si256(last_chunk.as_mut_ptr() as _, result);
+                        write.write_all(&last_chunk[0..remainder.len()])?;
+                    }
+
+                    Ok(())
+                }
+            }
+
+            else {
+                unsafe { bytes::write_slice(self, value)?; }
+                Ok(())
+            }
+        }
+
+        fn write_as_little_endian(&mut self, value: &[f32]) -> Result<()> {
+            for number in value {
+                self.write_as_little_endian(number)?;
+            }
+
+            Ok(())
+        }
+    }*/
+}
+
+
+/// Unsigned and signed 24-bit integers, which take up three bytes in a stream.
+///
+/// Also contains extension traits for converting
+/// whole buffers of packed 3-byte samples to and from slices of 32-bit integers.
+pub mod int24;
+
+/// Signed and unsigned fixed point numbers with a configurable number of fraction bits,
+/// as used by font, GPS and DSP formats.
+/// They are read and written as their underlying integer.
+pub mod fixed;
+
+/// Helper traits for processing primitive integers generically.
+pub mod integer;
+
+/// Variable-length integer encodings: `LEB128`, zigzag mapping and `VLQ`.
+pub mod varint;
+
+/// Read and write bitfields of any width, with either bit order.
+pub mod bits;
+
+/// Encode numbers as keys that sort in the same order as the numbers when compared byte by byte.
+pub mod memcomparable;
+
+/// Read and write UTF-16 and UTF-32 strings in either byte order, with optional byte order marks,
+/// and byte strings in fixed-size fields or with a null terminator.
+pub mod strings;
+
+/// Convert IBM System/360 hexadecimal floats, as used by SEG-Y and mainframe data, to and from `f32`.
+pub mod ibm;
+
+/// The 80-bit x87 extended precision float, as used by AIFF files, with exact conversions from `f64`.
+pub mod f80;
+
+/// Packed binary coded decimal numbers, with configurable nibble order and sign nibble.
+pub mod bcd;
+
+/// An error type that adds context to failed reads, like the type that was read and the number of missing bytes.
+pub mod error;
+
+/// A writer that counts its position and fills in offsets and sizes after the data they describe has been written,
+/// and a reader that counts its position, both of which can pad to aligned positions.
+pub mod positioned;
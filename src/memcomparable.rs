@@ -0,0 +1,246 @@
+use crate::io::invalid_data;
+use crate::int24::{ U24, I24 };
+use crate::fixed::{ FixedI8, FixedI16, FixedI32, FixedI64, FixedU8, FixedU16, FixedU32, FixedU64 };
+use std::io::Result;
+
+#[cfg(feature = "half")]
+use half::{ f16, bf16 };
+
+
+/// Values that can be encoded as bytes which sort in the same order as the values,
+/// when compared byte by byte using `memcmp`, as required by sorted key-value stores.
+///
+/// All encodings use the big endian byte order.
+/// Signed integers have their sign bit flipped, so that negative numbers sort first.
+/// Floats are sorted by their IEEE total order:
+/// negative nan, negative infinity, negative numbers, negative zero,
+/// positive zero, positive numbers, positive infinity, positive nan.
+///
+/// Implemented for all primitive integers and floats, `bool`, `char`,
+/// the 24-bit integers, the fixed point numbers, which sort like their underlying integer,
+/// and with the `half` feature, `f16` and `bf16`.
+pub trait Memcomparable: Sized {
+
+    /// A byte array with the same size as this value.
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Default + Copy;
+
+    /// Encode this value such that the bytes sort in ascending order.
+    fn to_memcomparable_bytes(&self) -> Self::Bytes;
+
+    /// Decode a value from bytes that were produced by `to_memcomparable_bytes`.
+    /// Returns an `InvalidData` error if the bytes do not represent a valid value.
+    fn from_memcomparable_bytes(bytes: Self::Bytes) -> Result<Self>;
+}
+
+/// Encode the value such that the bytes sort in ascending order.
+#[inline]
+pub fn encode_memcomparable<T: Memcomparable>(value: &T) -> T::Bytes {
+    value.to_memcomparable_bytes()
+}
+
+/// Encode the value such that the bytes sort in descending order.
+/// All bytes are inverted compared to `encode_memcomparable`.
+#[inline]
+pub fn encode_memcomparable_descending<T: Memcomparable>(value: &T) -> T::Bytes {
+    let mut bytes = value.to_memcomparable_bytes();
+    for byte in bytes.as_mut() { *byte = !*byte; }
+    bytes
+}
+
+/// Decode a value from bytes that were produced by `encode_memcomparable`.
+/// Returns an `InvalidData` error if the number of bytes does not match the type,
+/// or if the bytes do not represent a valid value.
+#[inline]
+pub fn decode_memcomparable<T: Memcomparable>(bytes: &[u8]) -> Result<T> {
+    T::from_memcomparable_bytes(copy_bytes::<T>(bytes)?)
+}
+
+/// Decode a value from bytes that were produced by `encode_memcomparable_descending`.
+/// Returns an `InvalidData` error if the number of bytes does not match the type,
+/// or if the bytes do not represent a valid value.
+#[inline]
+pub fn decode_memcomparable_descending<T: Memcomparable>(bytes: &[u8]) -> Result<T> {
+    let mut bytes = copy_bytes::<T>(bytes)?;
+    for byte in bytes.as_mut() { *byte = !*byte; }
+    T::from_memcomparable_bytes(bytes)
+}
+
+#[inline]
+fn copy_bytes<T: Memcomparable>(bytes: &[u8]) -> Result<T::Bytes> {
+    let mut array = T::Bytes::default();
+
+    if array.as_ref().len() != bytes.len() {
+        return Err(invalid_data("memcomparable key has the wrong number of bytes"));
+    }
+
+    array.as_mut().copy_from_slice(bytes);
+    Ok(array)
+}
+
+
+macro_rules! implement_unsigned_memcomparable {
+    ($( $type: ident ),* ) => {
+        $(
+            impl Memcomparable for $type {
+                type Bytes = [u8; std::mem::size_of::<$type>()];
+
+                #[inline]
+                fn to_memcomparable_bytes(&self) -> Self::Bytes {
+                    self.to_be_bytes()
+                }
+
+                #[inline]
+                fn from_memcomparable_bytes(bytes: Self::Bytes) -> Result<Self> {
+                    Ok($type::from_be_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+// flip the sign bit, so that negative numbers come first
+macro_rules! implement_signed_memcomparable {
+    ($( $type: ident : $unsigned: ident ),* ) => {
+        $(
+            impl Memcomparable for $type {
+                type Bytes = [u8; std::mem::size_of::<$type>()];
+
+                #[inline]
+                fn to_memcomparable_bytes(&self) -> Self::Bytes {
+                    (*self as $unsigned ^ (1 << ($unsigned::BITS - 1))).to_be_bytes()
+                }
+
+                #[inline]
+                fn from_memcomparable_bytes(bytes: Self::Bytes) -> Result<Self> {
+                    Ok(($unsigned::from_be_bytes(bytes) ^ (1 << ($unsigned::BITS - 1))) as $type)
+                }
+            }
+        )*
+    };
+}
+
+// flip the sign bit of positive numbers, so that they come after all negative numbers,
+// and flip all bits of negative numbers, so that larger magnitudes come first
+macro_rules! implement_float_memcomparable {
+    ($( $type: ident : $bits: ident ),* ) => {
+        $(
+            impl Memcomparable for $type {
+                type Bytes = [u8; std::mem::size_of::<$type>()];
+
+                #[inline]
+                fn to_memcomparable_bytes(&self) -> Self::Bytes {
+                    let bits = self.to_bits();
+                    let sign_bit = 1 << ($bits::BITS - 1);
+                    let ordered = if bits & sign_bit != 0 { !bits } else { bits ^ sign_bit };
+                    ordered.to_be_bytes()
+                }
+
+                #[inline]
+                fn from_memcomparable_bytes(bytes: Self::Bytes) -> Result<Self> {
+                    let ordered = $bits::from_be_bytes(bytes);
+                    let sign_bit = 1 << ($bits::BITS - 1);
+                    let bits = if ordered & sign_bit != 0 { ordered ^ sign_bit } else { !ordered };
+                    Ok($type::from_bits(bits))
+                }
+            }
+        )*
+    };
+}
+
+implement_unsigned_memcomparable!(u8, u16, u32, u64, u128);
+implement_signed_memcomparable!(i8: u8, i16: u16, i32: u32, i64: u64, i128: u128);
+implement_float_memcomparable!(f32: u32, f64: u64);
+
+#[cfg(feature = "half")]
+implement_float_memcomparable!(f16: u16, bf16: u16);
+
+// fixed point numbers sort like their underlying integer
+macro_rules! implement_fixed_memcomparable {
+    ($( $type: ident : $bits: ident ),* ) => {
+        $(
+            impl<const FRACTION: u32> Memcomparable for $type<FRACTION> {
+                type Bytes = <$bits as Memcomparable>::Bytes;
+
+                #[inline]
+                fn to_memcomparable_bytes(&self) -> Self::Bytes {
+                    self.to_bits().to_memcomparable_bytes()
+                }
+
+                #[inline]
+                fn from_memcomparable_bytes(bytes: Self::Bytes) -> Result<Self> {
+                    $bits::from_memcomparable_bytes(bytes).map($type::from_bits)
+                }
+            }
+        )*
+    };
+}
+
+implement_fixed_memcomparable!(
+    FixedI8: i8, FixedI16: i16, FixedI32: i32, FixedI64: i64,
+    FixedU8: u8, FixedU16: u16, FixedU32: u32, FixedU64: u64
+);
+
+impl Memcomparable for U24 {
+    type Bytes = [u8; 3];
+
+    #[inline]
+    fn to_memcomparable_bytes(&self) -> Self::Bytes {
+        self.to_be_bytes()
+    }
+
+    #[inline]
+    fn from_memcomparable_bytes(bytes: Self::Bytes) -> Result<Self> {
+        Ok(U24::from_be_bytes(bytes))
+    }
+}
+
+// flip the sign bit, which is the most significant bit of the first big endian byte
+impl Memcomparable for I24 {
+    type Bytes = [u8; 3];
+
+    #[inline]
+    fn to_memcomparable_bytes(&self) -> Self::Bytes {
+        let mut bytes = self.to_be_bytes();
+        bytes[0] ^= 0x80;
+        bytes
+    }
+
+    #[inline]
+    fn from_memcomparable_bytes(mut bytes: Self::Bytes) -> Result<Self> {
+        bytes[0] ^= 0x80;
+        Ok(I24::from_be_bytes(bytes))
+    }
+}
+
+impl Memcomparable for bool {
+    type Bytes = [u8; 1];
+
+    #[inline]
+    fn to_memcomparable_bytes(&self) -> Self::Bytes {
+        [u8::from(*self)]
+    }
+
+    #[inline]
+    fn from_memcomparable_bytes(bytes: Self::Bytes) -> Result<Self> {
+        match bytes {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(invalid_data("invalid bool value: neither zero nor one")),
+        }
+    }
+}
+
+impl Memcomparable for char {
+    type Bytes = [u8; 4];
+
+    #[inline]
+    fn to_memcomparable_bytes(&self) -> Self::Bytes {
+        u32::from(*self).to_be_bytes()
+    }
+
+    #[inline]
+    fn from_memcomparable_bytes(bytes: Self::Bytes) -> Result<Self> {
+        char::from_u32(u32::from_be_bytes(bytes))
+            .ok_or_else(|| invalid_data("invalid char value: surrogate or out of range"))
+    }
+}
@@ -0,0 +1,384 @@
+use crate::ByteOrder;
+use crate::io::{ WriteEndian, ReadPadding, WritePadding };
+use std::convert::TryFrom;
+use std::io::{ Read, BufRead, Write, Seek, SeekFrom, Result, Error, ErrorKind };
+use std::marker::PhantomData;
+use std::sync::atomic::{ AtomicUsize, Ordering };
+
+
+/// A `std::io::Write` wrapper that counts the bytes written through it,
+/// and supports reserving placeholders that are filled in later,
+/// for example offsets or chunk sizes in OpenEXR, TIFF or RIFF files.
+///
+/// If the writer was created using `seekable`, placeholders are filled in by seeking back to them.
+/// Otherwise, everything after the first unfilled placeholder is kept in an internal buffer,
+/// which is patched when filling the placeholder, and written as soon as all placeholders are filled.
+///
+/// Call `finish` after writing everything, which returns an error if a placeholder was never filled.
+#[derive(Debug)]
+pub struct PositionedWriter<W> {
+    inner: W,
+    id: usize,
+    position: u64,
+    start: u64,
+    seek: Option<SeekFn<W>>,
+    buffer: Vec<u8>,
+    buffer_start: u64,
+    filled: Vec<bool>,
+    unfilled_count: usize,
+}
+
+// captured when the writer is created, so that filling placeholders does not require `W: Seek`
+type SeekFn<W> = fn(&mut W, SeekFrom) -> Result<u64>;
+
+// identifies the writer that reserved a placeholder
+static NEXT_WRITER_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A reserved region in a `PositionedWriter`, which will contain a value of type `T`.
+/// Created by `PositionedWriter::reserve`, and filled in using `PositionedWriter::fill`.
+#[derive(Debug)]
+pub struct Placeholder<T> {
+    writer: usize,
+    index: usize,
+    position: u64,
+    len: usize,
+    byte_order: ByteOrder,
+    value_type: PhantomData<fn(T)>,
+}
+
+impl<T> Clone for Placeholder<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Placeholder<T> {}
+
+impl<T> Placeholder<T> {
+
+    /// The position of this placeholder, relative to the start of the writer.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// The byte order that the value will be written in.
+    #[inline]
+    pub fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+}
+
+impl<W: Write> PositionedWriter<W> {
+
+    /// Count the bytes written to the inner writer,
+    /// and keep everything after the first unfilled placeholder in a buffer.
+    pub fn new(inner: W) -> Self {
+        PositionedWriter {
+            inner, id: NEXT_WRITER_ID.fetch_add(1, Ordering::Relaxed),
+            position: 0, start: 0, seek: None,
+            buffer: Vec::new(), buffer_start: 0,
+            filled: Vec::new(), unfilled_count: 0,
+        }
+    }
+
+    /// Count the bytes written to the inner writer, starting at its current position,
+    /// and fill in placeholders by seeking back to them, without buffering anything.
+    pub fn seekable(mut inner: W) -> Result<Self> where W: Seek {
+        let start = inner.stream_position()?;
+
+        // positions in this writer are relative to the start, while the inner writer needs absolute positions
+        let mut writer = PositionedWriter::new(inner);
+        writer.seek = Some(|inner, position| inner.seek(position));
+        writer.start = start;
+        Ok(writer)
+    }
+
+    /// The number of bytes written through this writer, including the placeholders.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// The number of placeholders that have not been filled yet.
+    #[inline]
+    pub fn unfilled_placeholders(&self) -> usize {
+        self.unfilled_count
+    }
+
+    /// Access the inner writer.
+    /// Without seeking, it does not contain the buffered bytes after an unfilled placeholder yet.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Write zeroes, or the default value, for a value of type `T` in the specified byte order,
+    /// and return a placeholder that can be filled in later.
+    pub fn reserve<T: Default>(&mut self, byte_order: ByteOrder) -> Result<Placeholder<T>> where Vec<u8>: WriteEndian<T> {
+        let bytes = encode(&T::default(), byte_order)?;
+
+        // without seeking, everything from the first unfilled placeholder on is buffered
+        if self.seek.is_none() && self.unfilled_count == 0 && self.buffer.is_empty() {
+            self.buffer_start = self.position;
+        }
+
+        let placeholder = Placeholder {
+            writer: self.id, index: self.filled.len(), position: self.position,
+            len: bytes.len(), byte_order, value_type: PhantomData,
+        };
+
+        self.filled.push(false);
+        self.unfilled_count += 1;
+        self.write_all(&bytes)?;
+
+        Ok(placeholder)
+    }
+
+    /// Fill in a placeholder that was reserved by this writer.
+    /// Returns an `InvalidInput` error if the placeholder was reserved by a different writer or has already been filled,
+    /// or if the value does not take up the same number of bytes as the default value.
+    ///
+    /// After the last placeholder is filled, the buffered bytes are written.
+    /// If that fails, the placeholder still counts as filled, and the remaining buffered bytes
+    /// are written before anything else by the next call to `write`, `flush` or `finish`.
+    pub fn fill<T>(&mut self, placeholder: Placeholder<T>, value: &T) -> Result<()> where Vec<u8>: WriteEndian<T> {
+        if placeholder.writer != self.id {
+            return Err(Error::new(ErrorKind::InvalidInput, "placeholder was reserved by a different writer"));
+        }
+
+        if self.filled[placeholder.index] {
+            return Err(Error::new(ErrorKind::InvalidInput, "placeholder has already been filled"));
+        }
+
+        let bytes = encode(value, placeholder.byte_order)?;
+        if bytes.len() != placeholder.len {
+            return Err(Error::new(ErrorKind::InvalidInput, "value does not have the same size as the placeholder"));
+        }
+
+        match self.seek {
+            Some(seek) => {
+                seek(&mut self.inner, SeekFrom::Start(self.start + placeholder.position))?;
+                let written = self.inner.write_all(&bytes);
+
+                // return to the end even if writing failed, so that the position stays correct
+                seek(&mut self.inner, SeekFrom::Start(self.start + self.position))?;
+                written?;
+            },
+
+            None => {
+                // unfilled placeholders of this writer are never before the start of the buffer
+                let start = (placeholder.position - self.buffer_start) as usize;
+                self.buffer[start .. start + bytes.len()].copy_from_slice(&bytes);
+            },
+        }
+
+        self.filled[placeholder.index] = true;
+        self.unfilled_count -= 1;
+
+        if self.unfilled_count == 0 {
+            self.write_buffer()?;
+        }
+
+        Ok(())
+    }
+
+    // write the buffered bytes, keeping those that have not been written yet if writing fails
+    fn write_buffer(&mut self) -> Result<()> {
+        while !self.buffer.is_empty() {
+            match self.inner.write(&self.buffer) {
+                Ok(0) => return Err(Error::new(ErrorKind::WriteZero, "failed to write the buffered bytes")),
+                Ok(count) => {
+                    self.buffer.drain(.. count);
+                    self.buffer_start += count as u64;
+                },
+
+                Err(error) if error.kind() == ErrorKind::Interrupted => {},
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write zeroes until the position is a multiple of the alignment, for example to pad a record to four bytes.
+    /// Returns an `InvalidInput` error if the alignment is zero.
+    pub fn write_padding_to_alignment(&mut self, alignment: u64) -> Result<()> {
+        let padding = padding_to_alignment(self.position, alignment)?;
+        self.write_zeros(padding)
+    }
+
+    /// Flush the inner writer and return it.
+    /// Returns an `InvalidInput` error if a placeholder has not been filled.
+    pub fn finish(mut self) -> Result<W> {
+        if self.unfilled_count != 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, "placeholder was never filled"));
+        }
+
+        self.write_buffer()?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for PositionedWriter<W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<usize> {
+        let count =
+            if self.seek.is_none() && self.unfilled_count != 0 {
+                self.buffer.extend_from_slice(bytes);
+                bytes.len()
+            }
+            else {
+                // buffered bytes that could not be written earlier come first
+                self.write_buffer()?;
+                self.inner.write(bytes)?
+            };
+
+        self.position += count as u64;
+        Ok(count)
+    }
+
+    /// Flushes the inner writer. Buffered bytes after an unfilled placeholder are not written yet.
+    fn flush(&mut self) -> Result<()> {
+        if self.unfilled_count == 0 {
+            self.write_buffer()?;
+        }
+
+        self.inner.flush()
+    }
+}
+
+
+
+/// A `std::io::Read` wrapper that counts the bytes read through it,
+/// so that it can skip to aligned positions.
+///
+/// If the reader was created using `seekable`, skipping seeks instead of reading and discarding the bytes.
+/// The position is relative to the position of the inner reader when this reader was created.
+#[derive(Debug)]
+pub struct PositionedReader<R> {
+    inner: R,
+    position: u64,
+    start: u64,
+    end: u64,
+    seek: Option<SeekFn<R>>,
+}
+
+impl<R: Read> PositionedReader<R> {
+
+    /// Count the bytes read from the inner reader.
+    #[inline]
+    pub fn new(inner: R) -> Self {
+        PositionedReader { inner, position: 0, start: 0, end: 0, seek: None }
+    }
+
+    /// Count the bytes read from the inner reader, starting at its current position, and skip bytes by seeking.
+    /// The length of the stream is looked up once here, so that skipping can detect the end of the stream.
+    pub fn seekable(mut inner: R) -> Result<Self> where R: Seek {
+        let start = inner.stream_position()?;
+        let end = inner.seek(SeekFrom::End(0))?;
+        inner.seek(SeekFrom::Start(start))?;
+
+        Ok(PositionedReader { inner, position: 0, start, end, seek: Some(|inner, position| inner.seek(position)) })
+    }
+
+    /// The number of bytes read or skipped through this reader.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Access the inner reader.
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Return the inner reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Skip the specified number of bytes, by seeking if the reader was created using `seekable`,
+    /// or by reading and discarding them like `ReadPadding::skip_bytes` otherwise.
+    ///
+    /// Returns an `UnexpectedEof` error if the stream ends before all bytes were skipped.
+    /// When seeking, the reader is then positioned at the end of the stream, as it was when this reader was created.
+    /// Skipping zero bytes does nothing, so that aligning an aligned position does not discard the buffer of a `BufReader`.
+    pub fn skip_bytes(&mut self, count: u64) -> Result<()> {
+        if count == 0 { return Ok(()); }
+
+        match self.seek {
+            Some(seek) => {
+                let target = self.position.checked_add(count).and_then(|position| position.checked_add(self.start))
+                    .filter(|&target| i64::try_from(target).is_ok())
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "cannot seek that far"))?;
+
+                // seeking past the end would succeed, so stop at the end instead
+                let expected = target.min(self.end.max(self.start + self.position));
+                let reached = seek(&mut self.inner, SeekFrom::Start(expected))?;
+                self.position = reached.saturating_sub(self.start);
+
+                if reached != expected {
+                    return Err(Error::other("seeking did not reach the requested position"));
+                }
+
+                if reached != target {
+                    return Err(Error::new(ErrorKind::UnexpectedEof, "stream ended before all bytes were skipped"));
+                }
+
+                Ok(())
+            },
+
+            None => ReadPadding::skip_bytes(self, count),
+        }
+    }
+
+    /// Skip bytes until the position is a multiple of the alignment, for example to skip the padding after a record.
+    /// Returns an `InvalidInput` error if the alignment is zero.
+    pub fn align_reader_to(&mut self, alignment: u64) -> Result<()> {
+        let padding = padding_to_alignment(self.position, alignment)?;
+        self.skip_bytes(padding)
+    }
+}
+
+impl<R: Read> Read for PositionedReader<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> Result<usize> {
+        let count = self.inner.read(buffer)?;
+        self.position += count as u64;
+        Ok(count)
+    }
+}
+
+impl<R: BufRead> BufRead for PositionedReader<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.inner.consume(amount);
+        self.position += amount as u64;
+    }
+}
+
+
+// the number of bytes from the position to the next multiple of the alignment
+fn padding_to_alignment(position: u64, alignment: u64) -> Result<u64> {
+    if alignment == 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "alignment must not be zero"));
+    }
+
+    Ok((alignment - position % alignment) % alignment)
+}
+
+fn encode<T>(value: &T, byte_order: ByteOrder) -> Result<Vec<u8>> where Vec<u8>: WriteEndian<T> {
+    let mut bytes = Vec::new();
+
+    match byte_order {
+        ByteOrder::LittleEndian => bytes.write_as_little_endian(value)?,
+        ByteOrder::BigEndian => bytes.write_as_big_endian(value)?,
+    }
+
+    Ok(bytes)
+}
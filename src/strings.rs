@@ -0,0 +1,451 @@
+use crate::{ ByteOrder, Endian };
+use crate::io::{ ReadEndian, CHUNK_SIZE, write_converted, invalid_data };
+use crate::error::for_type;
+use std::io::{ Read, Write, Result, Error, ErrorKind };
+
+
+/// What to do with code units that do not form a valid unicode character,
+/// for example unpaired surrogates in UTF-16 or truncated sequences in UTF-8.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InvalidCharacters {
+
+    /// Return an `InvalidData` error.
+    Reject,
+
+    /// Replace each invalid character with the replacement character `U+FFFD`.
+    Replace,
+}
+
+/// How the unused bytes at the end of a fixed-size string field are filled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Padding {
+
+    /// Fill with zero bytes, like a C `char name[32]` field.
+    /// When reading, the string ends at the first zero byte, and everything after it is ignored.
+    Nul,
+
+    /// Fill with spaces. When reading, all trailing spaces are removed.
+    Space,
+
+    /// The string always occupies the whole field.
+    /// When reading, all bytes are returned, and when writing, the string must have exactly the size of the field.
+    None,
+}
+
+/// Detect the byte order from the first two bytes of a UTF-16 stream.
+/// Returns `None` if the bytes are not a byte order mark.
+#[inline]
+pub fn detect_utf16_byte_order_mark(bytes: [u8; 2]) -> Option<ByteOrder> {
+    match bytes {
+        [0xFF, 0xFE] => Some(ByteOrder::LittleEndian),
+        [0xFE, 0xFF] => Some(ByteOrder::BigEndian),
+        _ => None,
+    }
+}
+
+/// Detect the byte order from the first four bytes of a UTF-32 stream.
+/// Returns `None` if the bytes are not a byte order mark.
+#[inline]
+pub fn detect_utf32_byte_order_mark(bytes: [u8; 4]) -> Option<ByteOrder> {
+    match bytes {
+        [0xFF, 0xFE, 0x00, 0x00] => Some(ByteOrder::LittleEndian),
+        [0x00, 0x00, 0xFE, 0xFF] => Some(ByteOrder::BigEndian),
+        _ => None,
+    }
+}
+
+
+/// A `std::io::Read` input stream which supports reading UTF-16 and UTF-32 strings.
+///
+/// Lengths are counted in code units, which are two bytes for UTF-16 and four bytes for UTF-32.
+/// The code units are read using the fast slice path of `ReadEndian`.
+///
+/// This extension trait is implemented for all `Read` types.
+pub trait ReadUnicode {
+
+    /// Read a little endian UTF-16 string consisting of `len` code units.
+    fn read_utf16_from_little_endian(&mut self, len: usize, invalid: InvalidCharacters) -> Result<String>;
+
+    /// Read a big endian UTF-16 string consisting of `len` code units.
+    fn read_utf16_from_big_endian(&mut self, len: usize, invalid: InvalidCharacters) -> Result<String>;
+
+    /// Read a little endian UTF-16 string up to and including a null code unit.
+    /// Returns an `InvalidData` error if there is no null code unit within the first `max_len + 1` code units.
+    fn read_null_terminated_utf16_from_little_endian(&mut self, max_len: usize, invalid: InvalidCharacters) -> Result<String>;
+
+    /// Read a big endian UTF-16 string up to and including a null code unit.
+    /// Returns an `InvalidData` error if there is no null code unit within the first `max_len + 1` code units.
+    fn read_null_terminated_utf16_from_big_endian(&mut self, max_len: usize, invalid: InvalidCharacters) -> Result<String>;
+
+    /// Read a UTF-16 string consisting of `len` code units, including an optional leading byte order mark.
+    /// If there is no byte order mark, the fallback byte order is used. The byte order mark is not part of the string.
+    fn read_utf16_with_bom(&mut self, len: usize, fallback: ByteOrder, invalid: InvalidCharacters) -> Result<String>;
+
+    /// Read a little endian UTF-32 string consisting of `len` code units.
+    fn read_utf32_from_little_endian(&mut self, len: usize, invalid: InvalidCharacters) -> Result<String>;
+
+    /// Read a big endian UTF-32 string consisting of `len` code units.
+    fn read_utf32_from_big_endian(&mut self, len: usize, invalid: InvalidCharacters) -> Result<String>;
+
+    /// Read a little endian UTF-32 string up to and including a null code unit.
+    /// Returns an `InvalidData` error if there is no null code unit within the first `max_len + 1` code units.
+    fn read_null_terminated_utf32_from_little_endian(&mut self, max_len: usize, invalid: InvalidCharacters) -> Result<String>;
+
+    /// Read a big endian UTF-32 string up to and including a null code unit.
+    /// Returns an `InvalidData` error if there is no null code unit within the first `max_len + 1` code units.
+    fn read_null_terminated_utf32_from_big_endian(&mut self, max_len: usize, invalid: InvalidCharacters) -> Result<String>;
+
+    /// Read a UTF-32 string consisting of `len` code units, including an optional leading byte order mark.
+    /// If there is no byte order mark, the fallback byte order is used. The byte order mark is not part of the string.
+    fn read_utf32_with_bom(&mut self, len: usize, fallback: ByteOrder, invalid: InvalidCharacters) -> Result<String>;
+}
+
+/// A `std::io::Write` output stream which supports writing UTF-16 and UTF-32 strings.
+///
+/// The code units are collected into a buffer on the stack, which is converted at once and written in large blocks.
+///
+/// This extension trait is implemented for all `Write` types.
+pub trait WriteUnicode {
+
+    /// Write the string as little endian UTF-16.
+    fn write_utf16_as_little_endian(&mut self, text: &str) -> Result<()>;
+
+    /// Write the string as big endian UTF-16.
+    fn write_utf16_as_big_endian(&mut self, text: &str) -> Result<()>;
+
+    /// Write the string as little endian UTF-16, followed by a null code unit.
+    fn write_null_terminated_utf16_as_little_endian(&mut self, text: &str) -> Result<()>;
+
+    /// Write the string as big endian UTF-16, followed by a null code unit.
+    fn write_null_terminated_utf16_as_big_endian(&mut self, text: &str) -> Result<()>;
+
+    /// Write a byte order mark, followed by the string as UTF-16 in that byte order.
+    fn write_utf16_with_bom(&mut self, text: &str, byte_order: ByteOrder) -> Result<()>;
+
+    /// Write the string as little endian UTF-32.
+    fn write_utf32_as_little_endian(&mut self, text: &str) -> Result<()>;
+
+    /// Write the string as big endian UTF-32.
+    fn write_utf32_as_big_endian(&mut self, text: &str) -> Result<()>;
+
+    /// Write the string as little endian UTF-32, followed by a null code unit.
+    fn write_null_terminated_utf32_as_little_endian(&mut self, text: &str) -> Result<()>;
+
+    /// Write the string as big endian UTF-32, followed by a null code unit.
+    fn write_null_terminated_utf32_as_big_endian(&mut self, text: &str) -> Result<()>;
+
+    /// Write a byte order mark, followed by the string as UTF-32 in that byte order.
+    fn write_utf32_with_bom(&mut self, text: &str, byte_order: ByteOrder) -> Result<()>;
+}
+
+/// A `std::io::Read` input stream which supports reading fixed-size and null-terminated byte strings,
+/// as found in the headers of many file formats.
+///
+/// The string variants decode the bytes as UTF-8.
+/// As null-terminated strings are read byte by byte, consider wrapping unbuffered streams in a `std::io::BufReader`.
+///
+/// This extension trait is implemented for all `Read` types.
+pub trait ReadByteStrings: Read {
+
+    /// Read a field of exactly `len` bytes, and remove the padding.
+    fn read_fixed_bytes(&mut self, len: usize, padding: Padding) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        Read::take(&mut *self, len as u64).read_to_end(&mut bytes)?;
+
+        if bytes.len() != len {
+            return Err(crate::Error::unexpected_end(std::any::type_name::<Vec<u8>>(), len, bytes.len()).into());
+        }
+
+        match padding {
+            Padding::Nul => if let Some(end) = bytes.iter().position(|&byte| byte == 0) { bytes.truncate(end) },
+            Padding::Space => while bytes.last() == Some(&b' ') { bytes.pop(); },
+            Padding::None => {},
+        }
+
+        Ok(bytes)
+    }
+
+    /// Read a field of exactly `len` bytes, remove the padding, and decode the rest as UTF-8.
+    fn read_fixed_string(&mut self, len: usize, padding: Padding, invalid: InvalidCharacters) -> Result<String> {
+        decode_utf8(self.read_fixed_bytes(len, padding)?, invalid)
+    }
+
+    /// Read bytes up to and including a zero byte. The zero byte is not part of the result.
+    /// Returns an `InvalidData` error if there is no zero byte within the first `max_len + 1` bytes.
+    fn read_null_terminated_bytes(&mut self, max_len: usize) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        loop {
+            let mut byte = [0_u8];
+            crate::error::read_exact(self, &mut byte, std::any::type_name::<Vec<u8>>())?;
+
+            if byte[0] == 0 { return Ok(bytes); }
+            if bytes.len() == max_len { return Err(invalid_data("string is not null-terminated within the maximum length")); }
+            bytes.push(byte[0]);
+        }
+    }
+
+    /// Read bytes up to and including a zero byte, and decode them as UTF-8.
+    /// Returns an `InvalidData` error if there is no zero byte within the first `max_len + 1` bytes.
+    fn read_null_terminated_string(&mut self, max_len: usize, invalid: InvalidCharacters) -> Result<String> {
+        decode_utf8(self.read_null_terminated_bytes(max_len)?, invalid)
+    }
+}
+
+/// A `std::io::Write` output stream which supports writing fixed-size and null-terminated byte strings.
+///
+/// This extension trait is implemented for all `Write` types.
+pub trait WriteByteStrings: Write {
+
+    /// Write the bytes into a field of exactly `len` bytes, filling the rest with padding.
+    /// A null-padded string may occupy the whole field, leaving no room for a terminator.
+    /// Returns an `InvalidInput` error if the bytes do not fit into the field,
+    /// or if they are shorter than the field and the padding is `Padding::None`.
+    fn write_fixed_bytes(&mut self, bytes: &[u8], len: usize, padding: Padding) -> Result<()> {
+        if bytes.len() > len {
+            return Err(Error::new(ErrorKind::InvalidInput, "string is longer than the field"));
+        }
+
+        let fill = match padding {
+            Padding::Nul => 0,
+            Padding::Space => b' ',
+            Padding::None if bytes.len() == len => 0,
+            Padding::None => return Err(Error::new(ErrorKind::InvalidInput, "unpadded string is shorter than the field")),
+        };
+
+        self.write_all(bytes)?;
+        write_repeated(self, fill, len - bytes.len())
+    }
+
+    /// Write the UTF-8 bytes of the string into a field of exactly `len` bytes, filling the rest with padding.
+    /// Returns an `InvalidInput` error if the string does not fit into the field,
+    /// or if it is shorter than the field and the padding is `Padding::None`.
+    fn write_fixed_string(&mut self, text: &str, len: usize, padding: Padding) -> Result<()> {
+        self.write_fixed_bytes(text.as_bytes(), len, padding)
+    }
+
+    /// Write the bytes, followed by a zero byte.
+    /// Returns an `InvalidInput` error if the bytes contain a zero byte.
+    fn write_null_terminated_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        if bytes.contains(&0) {
+            return Err(Error::new(ErrorKind::InvalidInput, "null-terminated string contains a zero byte"));
+        }
+
+        self.write_all(bytes)?;
+        self.write_all(&[0])
+    }
+
+    /// Write the UTF-8 bytes of the string, followed by a zero byte.
+    /// Returns an `InvalidInput` error if the string contains a null character.
+    fn write_null_terminated_string(&mut self, text: &str) -> Result<()> {
+        self.write_null_terminated_bytes(text.as_bytes())
+    }
+}
+
+impl<R: Read + ?Sized> ReadByteStrings for R {}
+impl<W: Write + ?Sized> WriteByteStrings for W {}
+
+impl<R: Read> ReadUnicode for R {
+    fn read_utf16_from_little_endian(&mut self, len: usize, invalid: InvalidCharacters) -> Result<String> {
+        decode_utf16(&read_units(self, Vec::new(), len, ByteOrder::LittleEndian)?, invalid)
+    }
+
+    fn read_utf16_from_big_endian(&mut self, len: usize, invalid: InvalidCharacters) -> Result<String> {
+        decode_utf16(&read_units(self, Vec::new(), len, ByteOrder::BigEndian)?, invalid)
+    }
+
+    fn read_null_terminated_utf16_from_little_endian(&mut self, max_len: usize, invalid: InvalidCharacters) -> Result<String> {
+        decode_utf16(&read_null_terminated_units(self, max_len, ByteOrder::LittleEndian)?, invalid)
+    }
+
+    fn read_null_terminated_utf16_from_big_endian(&mut self, max_len: usize, invalid: InvalidCharacters) -> Result<String> {
+        decode_utf16(&read_null_terminated_units(self, max_len, ByteOrder::BigEndian)?, invalid)
+    }
+
+    fn read_utf16_with_bom(&mut self, len: usize, fallback: ByteOrder, invalid: InvalidCharacters) -> Result<String> {
+        if len == 0 { return Ok(String::new()); }
+
+        let mut first = [0_u8; 2];
+        crate::error::read_exact(self, &mut first, std::any::type_name::<String>())?;
+
+        let units = match detect_utf16_byte_order_mark(first) {
+            Some(byte_order) => read_units(self, Vec::new(), len - 1, byte_order)?,
+            None => {
+                let first = match fallback {
+                    ByteOrder::LittleEndian => u16::from_le_bytes(first),
+                    ByteOrder::BigEndian => u16::from_be_bytes(first),
+                };
+
+                read_units(self, vec![ first ], len - 1, fallback)?
+            },
+        };
+
+        decode_utf16(&units, invalid)
+    }
+
+    fn read_utf32_from_little_endian(&mut self, len: usize, invalid: InvalidCharacters) -> Result<String> {
+        decode_utf32(&read_units(self, Vec::new(), len, ByteOrder::LittleEndian)?, invalid)
+    }
+
+    fn read_utf32_from_big_endian(&mut self, len: usize, invalid: InvalidCharacters) -> Result<String> {
+        decode_utf32(&read_units(self, Vec::new(), len, ByteOrder::BigEndian)?, invalid)
+    }
+
+    fn read_null_terminated_utf32_from_little_endian(&mut self, max_len: usize, invalid: InvalidCharacters) -> Result<String> {
+        decode_utf32(&read_null_terminated_units(self, max_len, ByteOrder::LittleEndian)?, invalid)
+    }
+
+    fn read_null_terminated_utf32_from_big_endian(&mut self, max_len: usize, invalid: InvalidCharacters) -> Result<String> {
+        decode_utf32(&read_null_terminated_units(self, max_len, ByteOrder::BigEndian)?, invalid)
+    }
+
+    fn read_utf32_with_bom(&mut self, len: usize, fallback: ByteOrder, invalid: InvalidCharacters) -> Result<String> {
+        if len == 0 { return Ok(String::new()); }
+
+        let mut first = [0_u8; 4];
+        crate::error::read_exact(self, &mut first, std::any::type_name::<String>())?;
+
+        let units = match detect_utf32_byte_order_mark(first) {
+            Some(byte_order) => read_units(self, Vec::new(), len - 1, byte_order)?,
+            None => {
+                let first = match fallback {
+                    ByteOrder::LittleEndian => u32::from_le_bytes(first),
+                    ByteOrder::BigEndian => u32::from_be_bytes(first),
+                };
+
+                read_units(self, vec![ first ], len - 1, fallback)?
+            },
+        };
+
+        decode_utf32(&units, invalid)
+    }
+}
+
+impl<W: Write> WriteUnicode for W {
+    fn write_utf16_as_little_endian(&mut self, text: &str) -> Result<()> {
+        write_units::<_, u16>(self, text.encode_utf16(), ByteOrder::LittleEndian)
+    }
+
+    fn write_utf16_as_big_endian(&mut self, text: &str) -> Result<()> {
+        write_units::<_, u16>(self, text.encode_utf16(), ByteOrder::BigEndian)
+    }
+
+    fn write_null_terminated_utf16_as_little_endian(&mut self, text: &str) -> Result<()> {
+        write_units::<_, u16>(self, text.encode_utf16().chain(Some(0)), ByteOrder::LittleEndian)
+    }
+
+    fn write_null_terminated_utf16_as_big_endian(&mut self, text: &str) -> Result<()> {
+        write_units::<_, u16>(self, text.encode_utf16().chain(Some(0)), ByteOrder::BigEndian)
+    }
+
+    fn write_utf16_with_bom(&mut self, text: &str, byte_order: ByteOrder) -> Result<()> {
+        write_units::<_, u16>(self, Some(0xFEFF).into_iter().chain(text.encode_utf16()), byte_order)
+    }
+
+    fn write_utf32_as_little_endian(&mut self, text: &str) -> Result<()> {
+        write_units::<_, u32>(self, text.chars().map(u32::from), ByteOrder::LittleEndian)
+    }
+
+    fn write_utf32_as_big_endian(&mut self, text: &str) -> Result<()> {
+        write_units::<_, u32>(self, text.chars().map(u32::from), ByteOrder::BigEndian)
+    }
+
+    fn write_null_terminated_utf32_as_little_endian(&mut self, text: &str) -> Result<()> {
+        write_units::<_, u32>(self, text.chars().map(u32::from).chain(Some(0)), ByteOrder::LittleEndian)
+    }
+
+    fn write_null_terminated_utf32_as_big_endian(&mut self, text: &str) -> Result<()> {
+        write_units::<_, u32>(self, text.chars().map(u32::from).chain(Some(0)), ByteOrder::BigEndian)
+    }
+
+    fn write_utf32_with_bom(&mut self, text: &str, byte_order: ByteOrder) -> Result<()> {
+        write_units::<_, u32>(self, Some(0xFEFF).into_iter().chain(text.chars().map(u32::from)), byte_order)
+    }
+}
+
+
+// read the code units in chunks, so that a corrupt length does not allocate huge amounts of memory up front
+fn read_units<R, T>(read: &mut R, mut units: Vec<T>, len: usize, byte_order: ByteOrder) -> Result<Vec<T>>
+    where R: ReadEndian<[T]>, T: Copy + Default
+{
+    let end = units.len() + len;
+
+    while units.len() < end {
+        let start = units.len();
+        units.resize(start + (end - start).min(CHUNK_SIZE), T::default());
+
+        match byte_order {
+            ByteOrder::LittleEndian => read.read_from_little_endian_into(&mut units[start ..]),
+            ByteOrder::BigEndian => read.read_from_big_endian_into(&mut units[start ..]),
+        }.map_err(for_type::<String>)?;
+    }
+
+    Ok(units)
+}
+
+fn read_null_terminated_units<R, T>(read: &mut R, max_len: usize, byte_order: ByteOrder) -> Result<Vec<T>>
+    where R: ReadEndian<T>, T: Copy + Default + PartialEq
+{
+    let mut units = Vec::new();
+
+    loop {
+        let unit = match byte_order {
+            ByteOrder::LittleEndian => read.read_from_little_endian(),
+            ByteOrder::BigEndian => read.read_from_big_endian(),
+        }.map_err(for_type::<String>)?;
+
+        if unit == T::default() { return Ok(units); }
+        if units.len() == max_len { return Err(invalid_data("string is not null-terminated within the maximum length")); }
+        units.push(unit);
+    }
+}
+
+// only used for `u16` and `u32`, which can be reinterpreted as bytes
+fn write_units<W: Write, T: Copy>(write: &mut W, units: impl Iterator<Item = T>, byte_order: ByteOrder) -> Result<()>
+    where [T]: Endian
+{
+    unsafe {
+        write_converted(write, units, |chunk: &mut [T]| match byte_order {
+            ByteOrder::LittleEndian => chunk.convert_current_to_little_endian(),
+            ByteOrder::BigEndian => chunk.convert_current_to_big_endian(),
+        })
+    }
+}
+
+fn write_repeated<W: Write + ?Sized>(write: &mut W, byte: u8, mut count: usize) -> Result<()> {
+    let buffer = [byte; 256];
+
+    while count > 0 {
+        let len = count.min(buffer.len());
+        write.write_all(&buffer[.. len])?;
+        count -= len;
+    }
+
+    Ok(())
+}
+
+fn decode_utf8(bytes: Vec<u8>, invalid: InvalidCharacters) -> Result<String> {
+    match invalid {
+        InvalidCharacters::Replace => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+        InvalidCharacters::Reject => String::from_utf8(bytes)
+            .map_err(|_| invalid_data("invalid UTF-8")),
+    }
+}
+
+fn decode_utf16(units: &[u16], invalid: InvalidCharacters) -> Result<String> {
+    match invalid {
+        InvalidCharacters::Replace => Ok(String::from_utf16_lossy(units)),
+        InvalidCharacters::Reject => String::from_utf16(units)
+            .map_err(|_| invalid_data("invalid UTF-16: unpaired surrogate")),
+    }
+}
+
+fn decode_utf32(units: &[u32], invalid: InvalidCharacters) -> Result<String> {
+    units.iter()
+        .map(|&unit| match (char::from_u32(unit), invalid) {
+            (Some(character), _) => Ok(character),
+            (None, InvalidCharacters::Replace) => Ok(char::REPLACEMENT_CHARACTER),
+            (None, InvalidCharacters::Reject) => Err(invalid_data("invalid UTF-32: surrogate or out of range")),
+        })
+        .collect()
+}
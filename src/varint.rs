@@ -0,0 +1,316 @@
+use crate::integer::{ Unsigned, Signed };
+use crate::io::invalid_data;
+use std::io::{ Read, Write, Result };
+
+
+// the maximum number of bytes of any encoding in this module, required for 128-bit integers
+const MAX_ENCODED_LEN: usize = 19;
+
+// the size of the buffer on the stack that is used to write slices in large blocks
+const BUFFER_LEN: usize = 4096;
+
+
+/// Map a signed integer to an unsigned integer, such that values with a small magnitude
+/// result in small numbers: `0, -1, 1, -2, 2` become `0, 1, 2, 3, 4`.
+/// This is how protobuf stores signed integers.
+#[inline]
+pub fn zigzag_encode<T: Signed>(value: T) -> T::Unsigned {
+    let value = value.to_i128();
+    let bits = (value.wrapping_shl(1) ^ (value >> 127)) as u128;
+    T::Unsigned::from_u128_truncated(bits)
+}
+
+/// Reverts `zigzag_encode`: `0, 1, 2, 3, 4` become `0, -1, 1, -2, 2`.
+#[inline]
+pub fn zigzag_decode<T: Unsigned>(value: T) -> T::Signed {
+    let bits = value.to_u128();
+    let value = (bits >> 1) as i128 ^ -((bits & 1) as i128);
+    T::Signed::from_i128_truncated(value)
+}
+
+
+/// A `std::io::Write` output stream which supports writing integers with a variable number of bytes.
+/// Small values take up fewer bytes than large values.
+///
+/// Supports the little endian `LEB128` encodings used by WebAssembly, DWARF and protobuf,
+/// and the big endian `VLQ` encoding used by MIDI.
+///
+/// This extension trait is implemented for all `Write` types.
+/// The slice variants encode into a buffer and write it in large blocks.
+pub trait WriteVarInt: Write {
+
+    /// Write an unsigned integer as unsigned `LEB128`, using seven bits per byte, least significant group first.
+    fn write_uleb128<T: Unsigned>(&mut self, value: T) -> Result<()> {
+        write_encoded(self, value.to_u128(), encode_uleb128)
+    }
+
+    /// Write a signed integer as signed `LEB128`, using seven bits per byte, least significant group first.
+    fn write_sleb128<T: Signed>(&mut self, value: T) -> Result<()> {
+        write_encoded(self, value.to_i128(), encode_sleb128)
+    }
+
+    /// Write a signed integer as unsigned `LEB128`, after mapping it using `zigzag_encode`.
+    fn write_zigzag_uleb128<T: Signed>(&mut self, value: T) -> Result<()> {
+        self.write_uleb128(zigzag_encode(value))
+    }
+
+    /// Write an unsigned integer as `VLQ`, using seven bits per byte, most significant group first.
+    fn write_vlq<T: Unsigned>(&mut self, value: T) -> Result<()> {
+        write_encoded(self, value.to_u128(), encode_vlq)
+    }
+
+    /// Write each integer as unsigned `LEB128`.
+    fn write_uleb128_slice<T: Unsigned>(&mut self, values: &[T]) -> Result<()> {
+        write_encoded_slice(self, values, |value, output| encode_uleb128(value.to_u128(), output))
+    }
+
+    /// Write each integer as signed `LEB128`.
+    fn write_sleb128_slice<T: Signed>(&mut self, values: &[T]) -> Result<()> {
+        write_encoded_slice(self, values, |value, output| encode_sleb128(value.to_i128(), output))
+    }
+
+    /// Write each integer as unsigned `LEB128`, after mapping it using `zigzag_encode`.
+    fn write_zigzag_uleb128_slice<T: Signed>(&mut self, values: &[T]) -> Result<()> {
+        write_encoded_slice(self, values, |value, output| encode_uleb128(zigzag_encode(value).to_u128(), output))
+    }
+
+    /// Write each integer as `VLQ`.
+    fn write_vlq_slice<T: Unsigned>(&mut self, values: &[T]) -> Result<()> {
+        write_encoded_slice(self, values, |value, output| encode_vlq(value.to_u128(), output))
+    }
+}
+
+/// A `std::io::Read` input stream which supports reading integers with a variable number of bytes.
+///
+/// Returns an `InvalidData` error if the value does not fit into the requested type,
+/// or if the value was encoded with more bytes than necessary.
+/// As the bytes are read one by one, consider wrapping unbuffered streams in a `std::io::BufReader`.
+///
+/// This extension trait is implemented for all `Read` types.
+pub trait ReadVarInt: Read {
+
+    /// Read an unsigned `LEB128` integer of the inferred type.
+    fn read_uleb128<T: Unsigned>(&mut self) -> Result<T> {
+        decode_uleb128(self)
+    }
+
+    /// Read a signed `LEB128` integer of the inferred type.
+    fn read_sleb128<T: Signed>(&mut self) -> Result<T> {
+        decode_sleb128(self)
+    }
+
+    /// Read an unsigned `LEB128` integer and map it to a signed integer using `zigzag_decode`.
+    fn read_zigzag_uleb128<T: Signed>(&mut self) -> Result<T> {
+        Ok(zigzag_decode(self.read_uleb128::<T::Unsigned>()?))
+    }
+
+    /// Read a `VLQ` integer of the inferred type.
+    fn read_vlq<T: Unsigned>(&mut self) -> Result<T> {
+        decode_vlq(self)
+    }
+
+    /// Fill the slice with unsigned `LEB128` integers.
+    fn read_uleb128_into<T: Unsigned>(&mut self, values: &mut [T]) -> Result<()> {
+        for value in values { *value = self.read_uleb128()?; }
+        Ok(())
+    }
+
+    /// Fill the slice with signed `LEB128` integers.
+    fn read_sleb128_into<T: Signed>(&mut self, values: &mut [T]) -> Result<()> {
+        for value in values { *value = self.read_sleb128()?; }
+        Ok(())
+    }
+
+    /// Fill the slice with zigzag mapped unsigned `LEB128` integers.
+    fn read_zigzag_uleb128_into<T: Signed>(&mut self, values: &mut [T]) -> Result<()> {
+        for value in values { *value = self.read_zigzag_uleb128()?; }
+        Ok(())
+    }
+
+    /// Fill the slice with `VLQ` integers.
+    fn read_vlq_into<T: Unsigned>(&mut self, values: &mut [T]) -> Result<()> {
+        for value in values { *value = self.read_vlq()?; }
+        Ok(())
+    }
+}
+
+impl<W: Write + ?Sized> WriteVarInt for W {}
+impl<R: Read + ?Sized> ReadVarInt for R {}
+
+
+#[inline]
+fn write_encoded<W: Write + ?Sized, T>(write: &mut W, value: T, encode: impl Fn(T, &mut [u8]) -> usize) -> Result<()> {
+    let mut buffer = [0_u8; MAX_ENCODED_LEN];
+    let len = encode(value, &mut buffer);
+    write.write_all(&buffer[.. len])
+}
+
+#[inline]
+fn write_encoded_slice<W: Write + ?Sized, T: Copy>(write: &mut W, values: &[T], encode: impl Fn(T, &mut [u8]) -> usize) -> Result<()> {
+    let mut buffer = [0_u8; BUFFER_LEN];
+    let mut len = 0;
+
+    for &value in values {
+        if BUFFER_LEN - len < MAX_ENCODED_LEN {
+            write.write_all(&buffer[.. len])?;
+            len = 0;
+        }
+
+        len += encode(value, &mut buffer[len ..]);
+    }
+
+    write.write_all(&buffer[.. len])
+}
+
+#[inline]
+fn encode_uleb128(mut value: u128, output: &mut [u8]) -> usize {
+    let mut len = 0;
+
+    loop {
+        let group = (value & 0x7F) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            output[len] = group;
+            return len + 1;
+        }
+
+        output[len] = group | 0x80;
+        len += 1;
+    }
+}
+
+#[inline]
+fn encode_sleb128(mut value: i128, output: &mut [u8]) -> usize {
+    let mut len = 0;
+
+    loop {
+        let group = (value & 0x7F) as u8;
+        value >>= 7;
+
+        // stop as soon as the remaining bits are the sign extension of the current group
+        let sign_bit = group & 0x40 != 0;
+        if (value == 0 && !sign_bit) || (value == -1 && sign_bit) {
+            output[len] = group;
+            return len + 1;
+        }
+
+        output[len] = group | 0x80;
+        len += 1;
+    }
+}
+
+#[inline]
+fn encode_vlq(value: u128, output: &mut [u8]) -> usize {
+    let significant_bits = (128 - value.leading_zeros()).max(1);
+    let len = (significant_bits as usize).div_ceil(7);
+
+    for (index, byte) in output[.. len].iter_mut().enumerate() {
+        let shift = 7 * (len - 1 - index);
+        let continuation = if index + 1 < len { 0x80 } else { 0 };
+        *byte = ((value >> shift) & 0x7F) as u8 | continuation;
+    }
+
+    len
+}
+
+#[inline]
+fn read_byte<R: Read + ?Sized>(read: &mut R) -> Result<u8> {
+    let mut byte = [0_u8];
+    read.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+fn decode_uleb128<T: Unsigned, R: Read + ?Sized>(read: &mut R) -> Result<T> {
+    let mut result = 0_u128;
+    let mut shift = 0;
+
+    loop {
+        let byte = read_byte(read)?;
+        let group = byte & 0x7F;
+
+        if shift >= T::BITS {
+            return Err(if group == 0 { invalid_data("overlong LEB128 encoding") } else { invalid_data("LEB128 value does not fit into the target type") });
+        }
+
+        // the last group may contain bits that do not fit into the type
+        if shift + 7 > T::BITS && group >> (T::BITS - shift) != 0 {
+            return Err(invalid_data("LEB128 value does not fit into the target type"));
+        }
+
+        result |= u128::from(group) << shift;
+
+        if byte & 0x80 == 0 {
+            if byte == 0 && shift != 0 { return Err(invalid_data("overlong LEB128 encoding")); }
+            return Ok(T::from_u128_truncated(result));
+        }
+
+        shift += 7;
+    }
+}
+
+fn decode_sleb128<T: Signed, R: Read + ?Sized>(read: &mut R) -> Result<T> {
+    let mut result = 0_u128;
+    let mut shift = 0;
+    let mut previous = 0_u8;
+
+    // whether this group only repeats the sign bit of the previous group
+    let is_sign_extension = |group: u8, previous: u8|
+        (group == 0 && previous & 0x40 == 0) || (group == 0x7F && previous & 0x40 != 0);
+
+    loop {
+        let byte = read_byte(read)?;
+        let group = byte & 0x7F;
+
+        if shift >= T::BITS {
+            return Err(if is_sign_extension(group, previous) { invalid_data("overlong LEB128 encoding") } else { invalid_data("LEB128 value does not fit into the target type") });
+        }
+
+        // in the last group, the sign bit of the type and all bits above must be equal
+        if shift + 7 > T::BITS {
+            let upper_bits = group >> (T::BITS - shift - 1);
+            let all_ones = 0x7F >> (T::BITS - shift - 1);
+
+            if upper_bits != 0 && upper_bits != all_ones {
+                return Err(invalid_data("LEB128 value does not fit into the target type"));
+            }
+        }
+
+        result |= u128::from(group) << shift;
+        shift += 7;
+
+        if byte & 0x80 == 0 {
+            if shift != 7 && is_sign_extension(group, previous) {
+                return Err(invalid_data("overlong LEB128 encoding"));
+            }
+
+            if shift < 128 && group & 0x40 != 0 {
+                result |= !0 << shift;
+            }
+
+            return Ok(T::from_i128_truncated(result as i128));
+        }
+
+        previous = byte;
+    }
+}
+
+fn decode_vlq<T: Unsigned, R: Read + ?Sized>(read: &mut R) -> Result<T> {
+    let first = read_byte(read)?;
+    if first == 0x80 { return Err(invalid_data("overlong VLQ encoding")); }
+
+    let mut byte = first;
+    let mut result = u128::from(first & 0x7F);
+
+    while byte & 0x80 != 0 {
+        byte = read_byte(read)?;
+
+        if result >> (T::BITS - 7) != 0 {
+            return Err(invalid_data("VLQ value does not fit into the target type"));
+        }
+
+        result = (result << 7) | u128::from(byte & 0x7F);
+    }
+
+    Ok(T::from_u128_truncated(result))
+}
@@ -1,7 +1,6 @@
 extern crate lebe;
 
 use lebe::prelude::*;
-use std::mem;
 
 use byteorder::{WriteBytesExt, LittleEndian, BigEndian, ReadBytesExt};
 
@@ -119,9 +118,14 @@ fn into_be_u32() {
 #[test]
 fn cmp_read_be_u16() {
     let read: &[u8] = &[0x33, 0xbb];
-    let a = u16::read_from_big_endian(&mut read.clone()).unwrap();
-    let b: u16 = read.clone().read_from_big_endian().unwrap();
-    let c = read.clone().read_u16::<BigEndian>().unwrap();
+    let mut reader = read;
+    let a = u16::read_from_big_endian(&mut reader).unwrap();
+
+    let mut reader = read;
+    let b: u16 = reader.read_from_big_endian().unwrap();
+
+    let mut reader = read;
+    let c = reader.read_u16::<BigEndian>().unwrap();
 
     assert_eq!(a, b);
     assert_eq!(a, c);
@@ -130,9 +134,14 @@ fn cmp_read_be_u16() {
 #[test]
 fn cmp_read_le_u16() {
     let read: &[u8] = &[0x33, 0xbb];
-    let a = u16::read_from_little_endian(&mut read.clone()).unwrap();
-    let b: u16 = read.clone().read_from_little_endian().unwrap();
-    let c = read.clone().read_u16::<LittleEndian>().unwrap();
+    let mut reader = read;
+    let a = u16::read_from_little_endian(&mut reader).unwrap();
+
+    let mut reader = read;
+    let b: u16 = reader.read_from_little_endian().unwrap();
+
+    let mut reader = read;
+    let c = reader.read_u16::<LittleEndian>().unwrap();
 
     assert_eq!(a, b);
     assert_eq!(a, c);
@@ -141,9 +150,14 @@ fn cmp_read_le_u16() {
 #[test]
 fn cmp_read_le_f32() {
     let read: &[u8] = &[0x33, 0xBB, 0x44, 0xCC];
-    let a = f32::read_from_little_endian(&mut read.clone()).unwrap();
-    let b: f32 = read.clone().read_from_little_endian().unwrap();
-    let c = read.clone().read_f32::<LittleEndian>().unwrap();
+    let mut reader = read;
+    let a = f32::read_from_little_endian(&mut reader).unwrap();
+
+    let mut reader = read;
+    let b: f32 = reader.read_from_little_endian().unwrap();
+
+    let mut reader = read;
+    let c = reader.read_f32::<LittleEndian>().unwrap();
 
     assert_eq!(a, b);
     assert_eq!(a, c);
@@ -208,4 +222,1342 @@ fn cmp_write_le_slice_u64() {
     write_actual.write_as_little_endian(data.as_slice()).unwrap();
 
     assert_eq!(write_actual, write_expected);
-}
\ No newline at end of file
+}
+
+#[test]
+fn read_write_bool_and_char() {
+    let mut bytes = Vec::new();
+    bytes.write_as_big_endian(&true).unwrap();
+    bytes.write_as_little_endian(&'€').unwrap();
+    bytes.write_as_big_endian(&'€').unwrap();
+    assert_eq!(bytes, [1, 0xAC, 0x20, 0, 0, 0, 0, 0x20, 0xAC]);
+
+    let mut read: &[u8] = &bytes;
+    assert!(bool::read_from_big_endian(&mut read).unwrap());
+    assert_eq!(char::read_from_little_endian(&mut read).unwrap(), '€');
+    assert_eq!(char::read_from_big_endian(&mut read).unwrap(), '€');
+}
+
+#[test]
+fn reject_invalid_bool_and_char() {
+    let error = bool::read_from_little_endian(&mut { &[2_u8] as &[u8] }).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+    let surrogate: &[u8] = &[0, 0, 0xD8, 0];
+    let error = char::read_from_big_endian(&mut { surrogate }).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn read_write_non_zero() {
+    use std::num::{NonZeroU32, NonZeroI16};
+
+    let mut bytes = Vec::new();
+    bytes.write_as_big_endian(&NonZeroU32::new(0x01020304).unwrap()).unwrap();
+    bytes.write_as_little_endian(&NonZeroI16::new(-2).unwrap()).unwrap();
+    assert_eq!(bytes, [1, 2, 3, 4, 0xFE, 0xFF]);
+
+    let mut read: &[u8] = &bytes;
+    let mut unsigned = NonZeroU32::MIN;
+    let mut signed = NonZeroI16::MIN;
+    read.read_from_big_endian_into(&mut unsigned).unwrap();
+    read.read_from_little_endian_into(&mut signed).unwrap();
+    assert_eq!(unsigned.get(), 0x01020304);
+    assert_eq!(signed.get(), -2);
+
+    let error = { &[0_u8, 0, 0, 0] as &[u8] }.read_from_little_endian_into(&mut unsigned).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn read_write_wrapping_slice() {
+    use std::num::Wrapping;
+
+    let data: Vec<Wrapping<u16>> = (0..300).map(|i: u16| Wrapping(i.wrapping_mul(257))).collect();
+    let plain: Vec<u16> = data.iter().map(|wrapping| wrapping.0).collect();
+
+    let mut write_expected = Vec::new();
+    let mut write_actual = Vec::new();
+    write_expected.write_as_big_endian(plain.as_slice()).unwrap();
+    write_actual.write_as_big_endian(data.as_slice()).unwrap();
+    assert_eq!(write_actual, write_expected);
+
+    let mut read_back = vec![ Wrapping(0_u16); data.len() ];
+    write_actual.as_slice().read_from_big_endian_into(read_back.as_mut_slice()).unwrap();
+    assert_eq!(read_back, data);
+
+    let mut swapped = [Wrapping(0x1234_u16), Wrapping(0x5678)];
+    swapped.swap_bytes();
+    assert_eq!(swapped, [Wrapping(0x3412), Wrapping(0x7856)]);
+}
+
+#[test]
+fn read_write_saturating() {
+    use std::num::Saturating;
+
+    let mut bytes = Vec::new();
+    bytes.write_as_little_endian(&Saturating(-3_i32)).unwrap();
+    assert_eq!(bytes, (-3_i32).to_le_bytes());
+
+    let read: Saturating<i32> = bytes.as_slice().read_from_little_endian().unwrap();
+    assert_eq!(read, Saturating(-3));
+}
+
+#[test]
+fn read_write_optional_non_zero() {
+    use std::num::NonZeroU64;
+
+    let offsets = [NonZeroU64::new(0x0102), None, NonZeroU64::new(7)];
+
+    let mut bytes = Vec::new();
+    bytes.write_as_big_endian(&offsets[..]).unwrap();
+    assert_eq!(&bytes[..16], &[0,0,0,0,0,0,1,2, 0,0,0,0,0,0,0,0]);
+
+    let mut read_back = [None; 3];
+    bytes.as_slice().read_from_big_endian_into(&mut read_back[..]).unwrap();
+    assert_eq!(read_back, offsets);
+
+    let single: Option<NonZeroU64> = { &bytes[8..] }.read_from_big_endian().unwrap();
+    assert_eq!(single, None);
+
+    let mut swapped = NonZeroU64::new(1);
+    swapped.swap_bytes();
+    assert_eq!(swapped, NonZeroU64::new(1 << 56));
+}
+
+#[test]
+fn read_write_ip_addresses() {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    let v4 = Ipv4Addr::new(192, 168, 0, 1);
+    let v6 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+
+    let mut little = Vec::new();
+    let mut big = Vec::new();
+    little.write_as_little_endian(&v4).unwrap();
+    little.write_as_little_endian(&v6).unwrap();
+    big.write_as_big_endian(&v4).unwrap();
+    big.write_as_big_endian(&v6).unwrap();
+
+    // addresses are not numbers and keep their octet order
+    assert_eq!(little, big);
+    assert_eq!(&big[..6], &[192, 168, 0, 1, 0x20, 0x01]);
+
+    let mut read: &[u8] = &big;
+    let mut read_v4 = Ipv4Addr::UNSPECIFIED;
+    let mut read_v6 = Ipv6Addr::UNSPECIFIED;
+    read.read_from_little_endian_into(&mut read_v4).unwrap();
+    read.read_from_big_endian_into(&mut read_v6).unwrap();
+    assert_eq!((read_v4, read_v6), (v4, v6));
+}
+
+#[test]
+fn read_write_socket_addresses() {
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+    let v4 = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 2), 0x1234);
+    let v6 = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 443, 0x000A_BCDE, 3);
+
+    let mut bytes = Vec::new();
+    bytes.write_as_little_endian(&v4).unwrap();
+    bytes.write_as_big_endian(&v6).unwrap();
+    assert_eq!(&bytes[..6], &[10, 0, 0, 2, 0x34, 0x12]);
+    assert_eq!(&bytes[6 + 16..], &[0x01, 0xBB, 0, 0x0A, 0xBC, 0xDE, 0, 0, 0, 3]);
+
+    let mut read: &[u8] = &bytes;
+    let mut read_v4 = SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0);
+    let mut read_v6 = SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0);
+    read.read_from_little_endian_into(&mut read_v4).unwrap();
+    read.read_from_big_endian_into(&mut read_v6).unwrap();
+    assert_eq!((read_v4, read_v6), (v4, v6));
+}
+
+#[test]
+fn int24_sign_extension() {
+    use lebe::int24::{I24, U24};
+
+    assert_eq!(I24::from_le_bytes([0xFF, 0xFF, 0xFF]).get(), -1);
+    assert_eq!(I24::from_be_bytes([0x80, 0x00, 0x00]), I24::MIN);
+    assert_eq!(I24::from_be_bytes([0x7F, 0xFF, 0xFF]), I24::MAX);
+    assert_eq!(I24::wrapping_from(0x0180_0000).get(), -0x0080_0000);
+    assert_eq!(I24::new(0x0080_0000), None);
+    assert_eq!(U24::from_be_bytes([0xAB, 0xCD, 0xEF]).get(), 0xABCDEF);
+    assert_eq!(U24::new(1 << 24), None);
+
+    let mut swapped = I24::from_be_bytes([0x01, 0x02, 0x83]);
+    swapped.swap_bytes();
+    assert_eq!(swapped, I24::from_be_bytes([0x83, 0x02, 0x01]));
+}
+
+#[test]
+fn read_write_int24() {
+    use lebe::int24::{I24, U24};
+
+    let mut bytes = Vec::new();
+    bytes.write_as_big_endian(&I24::new(-2).unwrap()).unwrap();
+    bytes.write_as_little_endian(&U24::new(0x010203).unwrap()).unwrap();
+    assert_eq!(bytes, [0xFF, 0xFF, 0xFE, 0x03, 0x02, 0x01]);
+
+    let mut read: &[u8] = &bytes;
+    assert_eq!(I24::read_from_big_endian(&mut read).unwrap().get(), -2);
+    assert_eq!(U24::read_from_little_endian(&mut read).unwrap().get(), 0x010203);
+}
+
+#[test]
+fn read_write_int24_samples() {
+    let samples: Vec<i32> = (0 .. 3000).map(|i| (i * 5591) % 0x0080_0000 - 0x0040_0000).collect();
+
+    let mut bytes = Vec::new();
+    bytes.write_i24_as_little_endian(&samples).unwrap();
+    assert_eq!(bytes.len(), samples.len() * 3);
+
+    let mut expected = Vec::new();
+    for &sample in &samples {
+        expected.write_i24::<LittleEndian>(sample).unwrap();
+    }
+
+    assert_eq!(bytes, expected);
+
+    let mut decoded = vec![0_i32; samples.len()];
+    bytes.as_slice().read_i24_from_little_endian_into(&mut decoded).unwrap();
+    assert_eq!(decoded, samples);
+
+    let error = Vec::new().write_i24_as_big_endian(&[0x0080_0000]).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn read_write_half_floats() {
+    use half::{f16, bf16};
+
+    let halves = [f16::from_f32(1.5), f16::from_f32(-0.25), f16::INFINITY];
+
+    let mut bytes = Vec::new();
+    bytes.write_as_big_endian(&halves[..]).unwrap();
+    assert_eq!(&bytes[..2], &f16::from_f32(1.5).to_be_bytes());
+
+    let mut read_back = [f16::ZERO; 3];
+    bytes.as_slice().read_from_big_endian_into(&mut read_back[..]).unwrap();
+    assert_eq!(read_back, halves);
+
+    let mut brain = Vec::new();
+    brain.write_as_little_endian(&bf16::from_f32(3.0)).unwrap();
+    assert_eq!(bf16::read_from_little_endian(&mut brain.as_slice()).unwrap(), bf16::from_f32(3.0));
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn read_half_floats_into_f32() {
+    use half::f16;
+
+    let floats: Vec<f32> = (0 .. 2500).map(|i| i as f32 * 0.5 - 600.0).collect();
+    let halves: Vec<f16> = floats.iter().map(|&float| f16::from_f32(float)).collect();
+
+    let mut little = Vec::new();
+    let mut big = Vec::new();
+    little.write_as_little_endian(halves.as_slice()).unwrap();
+    big.write_as_big_endian(halves.as_slice()).unwrap();
+
+    let mut from_little = vec![0.0_f32; floats.len()];
+    let mut from_big = vec![0.0_f32; floats.len()];
+    little.as_slice().read_f16_from_little_endian_into(&mut from_little).unwrap();
+    big.as_slice().read_f16_from_big_endian_into(&mut from_big).unwrap();
+
+    assert_eq!(from_little, floats);
+    assert_eq!(from_big, floats);
+}
+
+#[test]
+fn fixed_point_conversions() {
+    use lebe::fixed::{Fixed16Dot16, F2Dot14, FixedU8, OutOfRangeError};
+
+    assert_eq!(Fixed16Dot16::try_from_f64(1.5).unwrap().to_bits(), 0x0001_8000);
+    assert_eq!(Fixed16Dot16::from_bits(-0x0000_4000).to_f64(), -0.25);
+    assert_eq!(F2Dot14::from_bits(0x7FFF).to_f64(), 1.99993896484375);
+    assert_eq!(F2Dot14::try_from_f32(-2.0).unwrap(), F2Dot14::MIN);
+    assert_eq!(F2Dot14::try_from_f64(2.0), Err(OutOfRangeError));
+    assert_eq!(F2Dot14::try_from_f64(f64::NAN), Err(OutOfRangeError));
+    assert_eq!(FixedU8::<4>::try_from_f64(-0.01), Ok(FixedU8::from_bits(0)));
+    assert_eq!(FixedU8::<4>::try_from_f64(-0.1), Err(OutOfRangeError));
+
+    // rounds to the nearest value, with ties to even
+    assert_eq!(FixedU8::<1>::try_from_f64(0.25).unwrap().to_bits(), 0);
+    assert_eq!(FixedU8::<1>::try_from_f64(0.75).unwrap().to_bits(), 2);
+}
+
+#[test]
+fn read_write_fixed_point() {
+    use lebe::fixed::{Fixed16Dot16, F2Dot14};
+
+    let mut bytes = Vec::new();
+    bytes.write_as_big_endian(&Fixed16Dot16::try_from_f64(-1.5).unwrap()).unwrap();
+    bytes.write_as_little_endian(&[F2Dot14::from_bits(0x4000), F2Dot14::from_bits(-1)][..]).unwrap();
+    assert_eq!(bytes, [0xFF, 0xFE, 0x80, 0x00, 0x00, 0x40, 0xFF, 0xFF]);
+
+    let mut read: &[u8] = &bytes;
+    assert_eq!(Fixed16Dot16::read_from_big_endian(&mut read).unwrap().to_f64(), -1.5);
+
+    let mut fractions = [F2Dot14::default(); 2];
+    read.read_from_little_endian_into(&mut fractions[..]).unwrap();
+    assert_eq!(fractions[0].to_f32(), 1.0);
+}
+
+#[test]
+fn varint_known_encodings() {
+    let mut bytes = Vec::new();
+    bytes.write_uleb128(624485_u32).unwrap();
+    bytes.write_sleb128(-123456_i64).unwrap();
+    bytes.write_sleb128(64_i8).unwrap();
+    bytes.write_vlq(0x0FFF_FFFF_u32).unwrap();
+    bytes.write_vlq(0x80_u16).unwrap();
+    bytes.write_zigzag_uleb128(-2_i32).unwrap();
+
+    assert_eq!(bytes, [
+        0xE5, 0x8E, 0x26,
+        0xC0, 0xBB, 0x78,
+        0xC0, 0x00,
+        0xFF, 0xFF, 0xFF, 0x7F,
+        0x81, 0x00,
+        0x03,
+    ]);
+
+    let mut read: &[u8] = &bytes;
+    assert_eq!(read.read_uleb128::<u32>().unwrap(), 624485);
+    assert_eq!(read.read_sleb128::<i64>().unwrap(), -123456);
+    assert_eq!(read.read_sleb128::<i8>().unwrap(), 64);
+    assert_eq!(read.read_vlq::<u32>().unwrap(), 0x0FFF_FFFF);
+    assert_eq!(read.read_vlq::<u16>().unwrap(), 0x80);
+    assert_eq!(read.read_zigzag_uleb128::<i32>().unwrap(), -2);
+    assert!(read.is_empty());
+}
+
+#[test]
+fn varint_round_trip_extremes() {
+    let unsigned = [0, 1, 127, 128, u128::from(u64::MAX), u128::MAX - 1, u128::MAX];
+    let signed = [0, -1, 63, 64, -64, -65, i128::from(i64::MIN), i128::MIN, i128::MAX];
+
+    let mut bytes = Vec::new();
+    bytes.write_uleb128_slice(&unsigned).unwrap();
+    bytes.write_sleb128_slice(&signed).unwrap();
+    bytes.write_vlq_slice(&unsigned).unwrap();
+    bytes.write_zigzag_uleb128_slice(&signed).unwrap();
+    bytes.write_sleb128_slice(&[i8::MIN, i8::MAX]).unwrap();
+
+    let mut read: &[u8] = &bytes;
+    let (mut read_unsigned, mut read_signed) = ([0_u128; 7], [0_i128; 9]);
+
+    read.read_uleb128_into(&mut read_unsigned).unwrap();
+    assert_eq!(read_unsigned, unsigned);
+    read.read_sleb128_into(&mut read_signed).unwrap();
+    assert_eq!(read_signed, signed);
+    read.read_vlq_into(&mut read_unsigned).unwrap();
+    assert_eq!(read_unsigned, unsigned);
+    read.read_zigzag_uleb128_into(&mut read_signed).unwrap();
+    assert_eq!(read_signed, signed);
+
+    let mut small = [0_i8; 2];
+    read.read_sleb128_into(&mut small).unwrap();
+    assert_eq!(small, [i8::MIN, i8::MAX]);
+
+    assert_eq!(lebe::varint::zigzag_encode(i32::MIN), u32::MAX);
+    assert_eq!(lebe::varint::zigzag_decode(u32::MAX - 1), i32::MAX);
+}
+
+#[test]
+fn varint_rejects_overlong_and_overflow() {
+    use std::io::ErrorKind::InvalidData;
+
+    let overlong: [&[u8]; 3] = [&[0x80, 0x00], &[0xFF, 0x00], &[0x80, 0x80, 0x00]];
+    for bytes in overlong {
+        assert_eq!({ bytes }.read_uleb128::<u64>().unwrap_err().kind(), InvalidData);
+    }
+
+    assert_eq!({ &[0xFF_u8, 0x7F] as &[u8] }.read_sleb128::<i32>().unwrap_err().kind(), InvalidData);
+    assert_eq!({ &[0x80_u8, 0x00] as &[u8] }.read_vlq::<u32>().unwrap_err().kind(), InvalidData);
+
+    // 256 does not fit into a byte, but 255 does
+    assert_eq!({ &[0x80_u8, 0x02] as &[u8] }.read_uleb128::<u8>().unwrap_err().kind(), InvalidData);
+    assert_eq!({ &[0xFF_u8, 0x01] as &[u8] }.read_uleb128::<u8>().unwrap(), 255);
+
+    // -129 and 128 do not fit into a signed byte
+    assert_eq!({ &[0xFF_u8, 0x7E] as &[u8] }.read_sleb128::<i8>().unwrap_err().kind(), InvalidData);
+    assert_eq!({ &[0x80_u8, 0x01] as &[u8] }.read_sleb128::<i8>().unwrap_err().kind(), InvalidData);
+    assert_eq!({ &[0x82_u8, 0x80, 0x00] as &[u8] }.read_vlq::<u8>().unwrap_err().kind(), InvalidData);
+    assert_eq!({ &[0x80_u8, 0x80, 0x80, 0x80, 0x10] as &[u8] }.read_uleb128::<u32>().unwrap_err().kind(), InvalidData);
+}
+
+#[test]
+fn bit_orders() {
+    use lebe::bits::{BitWriter, BitOrder};
+
+    for (order, expected) in [(BitOrder::MostSignificantFirst, [0xB8, 0x80]), (BitOrder::LeastSignificantFirst, [0xC5, 0x01])] {
+        let mut writer = BitWriter::new(Vec::new(), order);
+        writer.write_bits(0b101_u8, 3).unwrap();
+        writer.write_bits(0b11000_u32, 5).unwrap();
+        writer.write_bit(true).unwrap();
+        assert_eq!(writer.finish().unwrap(), expected);
+    }
+}
+
+#[test]
+fn read_write_bits_round_trip() {
+    use lebe::bits::{BitReader, BitWriter, BitOrder};
+
+    for order in [BitOrder::MostSignificantFirst, BitOrder::LeastSignificantFirst] {
+        let mut writer = BitWriter::new(Vec::new(), order);
+        writer.write_bits(0x1ABC_u16, 13).unwrap();
+        writer.write_signed_bits(-3_i8, 4).unwrap();
+        writer.write_bits(u128::MAX - 5, 128).unwrap();
+        writer.byte_aligned().unwrap().write_as_big_endian(&0x0102_u16).unwrap();
+        writer.write_signed_bits(i64::MIN, 64).unwrap();
+
+        let bytes = writer.finish().unwrap();
+        let mut reader = BitReader::new(bytes.as_slice(), order);
+        assert_eq!(reader.read_bits::<u16>(13).unwrap(), 0x1ABC);
+        assert_eq!(reader.read_signed_bits::<i32>(4).unwrap(), -3);
+        assert_eq!(reader.read_bits::<u128>(128).unwrap(), u128::MAX - 5);
+        assert!(!reader.is_byte_aligned());
+        assert_eq!(u16::read_from_big_endian(reader.byte_aligned()).unwrap(), 0x0102);
+        assert_eq!(reader.read_signed_bits::<i64>(64).unwrap(), i64::MIN);
+    }
+}
+
+#[test]
+fn bits_reject_invalid_counts() {
+    use lebe::bits::{BitWriter, BitReader, BitOrder};
+    use std::io::ErrorKind::InvalidInput;
+
+    let mut writer = BitWriter::new(Vec::new(), BitOrder::MostSignificantFirst);
+    assert_eq!(writer.write_bits(8_u8, 3).unwrap_err().kind(), InvalidInput);
+    assert_eq!(writer.write_signed_bits(4_i8, 3).unwrap_err().kind(), InvalidInput);
+    assert_eq!(writer.write_bits(0_u8, 9).unwrap_err().kind(), InvalidInput);
+
+    let mut reader = BitReader::new(&[0xFF_u8][..], BitOrder::MostSignificantFirst);
+    assert_eq!(reader.read_bits::<u16>(17).unwrap_err().kind(), InvalidInput);
+    assert_eq!(reader.read_bits::<u16>(9).unwrap_err().kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn packed_12_bit_layout() {
+    let mut little = Vec::new();
+    let mut big = Vec::new();
+    little.write_packed_as_little_endian(12, &[0xABC_u16, 0xDEF]).unwrap();
+    big.write_packed_as_big_endian(12, &[0xABC_u16, 0xDEF]).unwrap();
+    assert_eq!(little, [0xBC, 0xFA, 0xDE]);
+    assert_eq!(big, [0xAB, 0xCD, 0xEF]);
+
+    let error = Vec::new().write_packed_as_big_endian(12, &[0x1000_u16]).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn packed_matches_bit_writer() {
+    use lebe::bits::{BitWriter, BitReader, BitOrder};
+
+    for bits in [1, 3, 7, 8, 10, 12, 14, 16, 24, 31, 32] {
+        // odd count to exercise partial groups, larger than one internal chunk
+        let values: Vec<u32> = (0 .. 2053_u32)
+            .map(|i| i.wrapping_mul(2_654_435_761) >> (32 - bits))
+            .collect();
+
+        for (order, big_endian) in [(BitOrder::MostSignificantFirst, true), (BitOrder::LeastSignificantFirst, false)] {
+            let mut expected = BitWriter::new(Vec::new(), order);
+            for &value in &values { expected.write_bits(value, bits).unwrap(); }
+            let expected = expected.finish().unwrap();
+
+            let mut packed = Vec::new();
+            if big_endian { packed.write_packed_as_big_endian(bits, &values).unwrap(); }
+            else { packed.write_packed_as_little_endian(bits, &values).unwrap(); }
+            assert_eq!(packed, expected, "packing {} bits", bits);
+
+            let mut unpacked = vec![0_u32; values.len()];
+            if big_endian { packed.as_slice().read_packed_from_big_endian_into(bits, &mut unpacked).unwrap(); }
+            else { packed.as_slice().read_packed_from_little_endian_into(bits, &mut unpacked).unwrap(); }
+            assert_eq!(unpacked, values, "unpacking {} bits", bits);
+
+            let mut reader = BitReader::new(packed.as_slice(), order);
+            assert_eq!(reader.read_bits::<u32>(bits).unwrap(), values[0]);
+        }
+    }
+}
+
+#[test]
+fn packed_into_u16() {
+    let samples: Vec<u16> = (0 .. 999).map(|i| (i * 37) % 1024).collect();
+
+    let mut bytes = Vec::new();
+    bytes.write_packed_as_little_endian(10, &samples).unwrap();
+    assert_eq!(bytes.len(), 1249);
+
+    let mut unpacked = vec![0_u16; samples.len()];
+    bytes.as_slice().read_packed_from_little_endian_into(10, &mut unpacked).unwrap();
+    assert_eq!(unpacked, samples);
+
+    let error = bytes.as_slice().read_packed_from_little_endian_into(17, &mut unpacked).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn memcomparable_integers_sort_like_values() {
+    use lebe::memcomparable::{encode_memcomparable, encode_memcomparable_descending, decode_memcomparable, decode_memcomparable_descending};
+
+    let mut values: Vec<i32> = vec![i32::MIN, -70000, -256, -1, 0, 1, 255, 256, 70000, i32::MAX];
+    let mut keys: Vec<[u8; 4]> = values.iter().map(encode_memcomparable).collect();
+    keys.reverse();
+    keys.sort();
+
+    let decoded: Vec<i32> = keys.iter().map(|key| decode_memcomparable(key).unwrap()).collect();
+    assert_eq!(decoded, values);
+
+    let mut descending: Vec<[u8; 4]> = values.iter().map(encode_memcomparable_descending).collect();
+    descending.sort();
+    values.reverse();
+
+    let decoded: Vec<i32> = descending.iter().map(|key| decode_memcomparable_descending(key).unwrap()).collect();
+    assert_eq!(decoded, values);
+
+    assert_eq!(encode_memcomparable(&0x0102_u16), [1, 2]);
+    assert!(decode_memcomparable::<u64>(&[1, 2, 3]).is_err());
+
+    use lebe::int24::I24;
+    use lebe::fixed::F2Dot14;
+
+    let small = [I24::MIN, I24::new(-1).unwrap(), I24::new(0).unwrap(), I24::MAX];
+    for pair in small.windows(2) {
+        assert!(encode_memcomparable(&pair[0]) < encode_memcomparable(&pair[1]));
+        assert_eq!(decode_memcomparable::<I24>(&encode_memcomparable(&pair[0])).unwrap(), pair[0]);
+    }
+
+    let fixed = [F2Dot14::MIN, F2Dot14::from_bits(-1), F2Dot14::from_bits(0), F2Dot14::MAX];
+    for pair in fixed.windows(2) {
+        assert!(encode_memcomparable(&pair[0]) < encode_memcomparable(&pair[1]));
+        assert_eq!(decode_memcomparable::<F2Dot14>(&encode_memcomparable(&pair[1])).unwrap(), pair[1]);
+    }
+}
+
+#[test]
+fn memcomparable_floats_use_total_order() {
+    use lebe::memcomparable::{encode_memcomparable, decode_memcomparable};
+
+    let values = [
+        -f64::NAN, f64::NEG_INFINITY, -1e300, -1.0, -f64::MIN_POSITIVE / 2.0, -0.0,
+        0.0, f64::MIN_POSITIVE / 2.0, 1.0, 1e300, f64::INFINITY, f64::NAN,
+    ];
+
+    for pair in values.windows(2) {
+        assert_eq!(pair[0].total_cmp(&pair[1]), std::cmp::Ordering::Less);
+        assert!(encode_memcomparable(&pair[0]) < encode_memcomparable(&pair[1]));
+    }
+
+    for value in values {
+        let decoded: f64 = decode_memcomparable(&encode_memcomparable(&value)).unwrap();
+        assert_eq!(decoded.to_bits(), value.to_bits());
+    }
+
+    assert!(encode_memcomparable(&-2.5_f32) < encode_memcomparable(&-2.4_f32));
+
+    #[cfg(feature = "half")] {
+        use half::f16;
+        assert!(encode_memcomparable(&f16::from_f32(-2.0)) < encode_memcomparable(&f16::from_f32(0.5)));
+        assert_eq!(decode_memcomparable::<f16>(&encode_memcomparable(&f16::NEG_INFINITY)).unwrap(), f16::NEG_INFINITY);
+    }
+}
+
+#[test]
+fn utf16_and_utf32_strings_round_trip() {
+    use lebe::strings::InvalidCharacters;
+
+    let text = "größe 🦀";
+    let units = text.encode_utf16().count();
+
+    let mut writer = Vec::new();
+    writer.write_utf16_as_big_endian(text).unwrap();
+    writer.write_null_terminated_utf16_as_little_endian(text).unwrap();
+    writer.write_utf32_as_little_endian(text).unwrap();
+    writer.write_null_terminated_utf32_as_big_endian(text).unwrap();
+
+    let expected: Vec<u8> = text.encode_utf16().flat_map(u16::to_be_bytes).collect();
+    assert_eq!(&writer[.. 2 * units], expected.as_slice());
+
+    let mut reader = writer.as_slice();
+    assert_eq!(reader.read_utf16_from_big_endian(units, InvalidCharacters::Reject).unwrap(), text);
+    assert_eq!(reader.read_null_terminated_utf16_from_little_endian(units, InvalidCharacters::Reject).unwrap(), text);
+    assert_eq!(reader.read_utf32_from_little_endian(text.chars().count(), InvalidCharacters::Reject).unwrap(), text);
+    assert_eq!(reader.read_null_terminated_utf32_from_big_endian(64, InvalidCharacters::Reject).unwrap(), text);
+    assert!(reader.is_empty());
+
+    let mut unterminated: &[u8] = &[b'a', 0, b'b', 0, b'c', 0, 0, 0];
+    assert!(unterminated.read_null_terminated_utf16_from_little_endian(2, InvalidCharacters::Reject).is_err());
+}
+
+#[test]
+fn unicode_byte_order_marks_and_invalid_characters() {
+    use lebe::ByteOrder;
+    use lebe::strings::InvalidCharacters;
+
+    let mut writer = Vec::new();
+    writer.write_utf16_with_bom("hi", ByteOrder::BigEndian).unwrap();
+    assert_eq!(writer, [0xFE, 0xFF, 0, b'h', 0, b'i']);
+    writer.write_utf32_with_bom("hi", ByteOrder::LittleEndian).unwrap();
+
+    let mut reader = writer.as_slice();
+    assert_eq!(reader.read_utf16_with_bom(3, ByteOrder::LittleEndian, InvalidCharacters::Reject).unwrap(), "hi");
+    assert_eq!(reader.read_utf32_with_bom(3, ByteOrder::BigEndian, InvalidCharacters::Reject).unwrap(), "hi");
+
+    // without a byte order mark, the fallback is used and the first code unit is kept
+    let mut reader: &[u8] = &[b'h', 0, b'i', 0];
+    assert_eq!(reader.read_utf16_with_bom(2, ByteOrder::LittleEndian, InvalidCharacters::Reject).unwrap(), "hi");
+
+    let unpaired_surrogate: &[u8] = &[0xD8, 0x00, 0x00, b'a'];
+    assert!({ unpaired_surrogate }.read_utf16_from_big_endian(2, InvalidCharacters::Reject).is_err());
+    assert_eq!({ unpaired_surrogate }.read_utf16_from_big_endian(2, InvalidCharacters::Replace).unwrap(), "\u{FFFD}a");
+
+    let out_of_range: &[u8] = &[0x00, 0x11, 0x00, 0x00];
+    assert!({ out_of_range }.read_utf32_from_big_endian(1, InvalidCharacters::Reject).is_err());
+    assert_eq!({ out_of_range }.read_utf32_from_big_endian(1, InvalidCharacters::Replace).unwrap(), "\u{FFFD}");
+}
+
+#[test]
+fn fixed_size_and_null_terminated_byte_strings() {
+    use lebe::strings::{Padding, InvalidCharacters};
+
+    let mut writer = Vec::new();
+    writer.write_fixed_string("name", 8, Padding::Nul).unwrap();
+    writer.write_fixed_string("ab", 4, Padding::Space).unwrap();
+    writer.write_fixed_bytes(b"full", 4, Padding::None).unwrap();
+    writer.write_null_terminated_string("héllo").unwrap();
+    assert_eq!(&writer[.. 16], b"name\0\0\0\0ab  full");
+
+    assert!(writer.write_fixed_string("too long", 4, Padding::Nul).is_err());
+    assert!(writer.write_fixed_bytes(b"ab", 4, Padding::None).is_err());
+    assert!(writer.write_null_terminated_bytes(b"a\0b").is_err());
+
+    let mut reader = writer.as_slice();
+    assert_eq!(reader.read_fixed_string(8, Padding::Nul, InvalidCharacters::Reject).unwrap(), "name");
+    assert_eq!(reader.read_fixed_bytes(4, Padding::Space).unwrap(), b"ab");
+    assert_eq!(reader.read_fixed_bytes(4, Padding::None).unwrap(), b"full");
+    assert_eq!(reader.read_null_terminated_string(6, InvalidCharacters::Reject).unwrap(), "héllo");
+    assert!(reader.is_empty());
+
+    // everything after the first zero byte is ignored
+    let mut reader: &[u8] = b"ab\0garbage\0";
+    assert_eq!(reader.read_fixed_bytes(8, Padding::Nul).unwrap(), b"ab");
+    assert!(reader.read_fixed_bytes(8, Padding::Nul).is_err());
+
+    let mut reader: &[u8] = b"abc\0";
+    assert!(reader.read_null_terminated_bytes(2).is_err());
+
+    let mut reader: &[u8] = b"a\xFFb\0";
+    assert_eq!(reader.read_null_terminated_string(8, InvalidCharacters::Replace).unwrap(), "a\u{FFFD}b");
+}
+
+#[test]
+fn ibm_floats_convert_known_values() {
+    use lebe::ibm::{ibm_f32_to_f32, f32_to_ibm_f32};
+
+    let known = [
+        (0x4110_0000_u32, 1.0_f32), (0x4264_0000, 100.0), (0xC276_A000, -118.625),
+        (0x0000_0000, 0.0), (0x4080_0000, 0.5), (0x3E10_0000, 1.0 / 4096.0),
+    ];
+
+    for (bits, value) in known {
+        assert_eq!(ibm_f32_to_f32(bits), value);
+        assert_eq!(f32_to_ibm_f32(value), Some(bits));
+    }
+
+    assert_eq!(f32_to_ibm_f32(-0.0), Some(0x8000_0000));
+    assert_eq!(f32_to_ibm_f32(f32::NAN), None);
+    assert_eq!(f32_to_ibm_f32(f32::INFINITY), None);
+
+    // outside of the f32 range
+    assert_eq!(ibm_f32_to_f32(0x7FFF_FFFF), f32::INFINITY);
+    assert_eq!(ibm_f32_to_f32(0xFFFF_FFFF), f32::NEG_INFINITY);
+    assert_eq!(ibm_f32_to_f32(0x0010_0000), 0.0);
+
+    // unnormalized fractions are accepted
+    assert_eq!(ibm_f32_to_f32(0x4201_0000), 1.0);
+
+    // the lowest bits of the f32 mantissa are rounded to nearest, ties to even
+    assert_eq!(f32_to_ibm_f32(f32::from_bits(0x3F80_0001)), Some(0x4110_0000));
+    assert_eq!(f32_to_ibm_f32(f32::from_bits(0x3F80_0004)), Some(0x4110_0000));
+    assert_eq!(f32_to_ibm_f32(f32::from_bits(0x3F80_000C)), Some(0x4110_0002));
+    assert_eq!(f32_to_ibm_f32(f32::from_bits(0x3FFF_FFFF)), Some(0x4120_0000));
+
+    // subnormal floats are normalized
+    let tiny = f32::from_bits(1);
+    assert_eq!(ibm_f32_to_f32(f32_to_ibm_f32(tiny).unwrap()), tiny);
+}
+
+#[test]
+fn ibm_float_slices_round_trip() {
+    let values: Vec<f32> = (0 .. 3000).map(|i| (i as f32 - 1500.0) * 0.0625).collect();
+
+    let mut writer = Vec::new();
+    writer.write_ibm_f32_as_big_endian(&values).unwrap();
+    writer.write_ibm_f32_as_little_endian(&values[.. 10]).unwrap();
+    assert_eq!(writer.len(), 4 * 3010);
+
+    let mut reader = writer.as_slice();
+    let mut decoded = vec![0.0_f32; 3000];
+    reader.read_ibm_f32_from_big_endian_into(&mut decoded).unwrap();
+    assert_eq!(decoded, values);
+
+    let mut decoded = [0.0_f32; 10];
+    reader.read_ibm_f32_from_little_endian_into(&mut decoded).unwrap();
+    assert_eq!(decoded, values[.. 10]);
+
+    assert!(Vec::new().write_ibm_f32_as_big_endian(&[1.0, f32::NAN]).is_err());
+}
+
+#[test]
+fn f80_reads_aiff_sample_rate_and_round_trips() {
+    use lebe::f80::F80;
+
+    // the sample rate of an AIFF file with 44100 Hz
+    let bytes = [0x40, 0x0E, 0xAC, 0x44, 0, 0, 0, 0, 0, 0];
+    let sample_rate: F80 = { &bytes[..] }.read_from_big_endian().unwrap();
+    assert_eq!(sample_rate.to_f64(), 44100.0);
+    assert_eq!(F80::from(44100.0_f64).to_be_bytes(), bytes);
+
+    let mut little_endian = bytes;
+    little_endian.reverse();
+    assert_eq!(F80::from_le_bytes(little_endian), sample_rate);
+
+    let values = [
+        0.0, -0.0, 1.0, -2.5, 1e300, -1e-300, f64::MAX, f64::MIN_POSITIVE,
+        f64::from_bits(1), f64::from_bits(0x000F_FFFF_FFFF_FFFF), f64::INFINITY, f64::NEG_INFINITY,
+    ];
+
+    let converted: Vec<F80> = values.iter().map(|&value| F80::from(value)).collect();
+    for (&value, &extended) in values.iter().zip(&converted) {
+        assert_eq!(extended.to_f64().to_bits(), value.to_bits());
+    }
+
+    let mut writer = Vec::new();
+    writer.write_as_little_endian(converted.as_slice()).unwrap();
+    writer.write_as_big_endian(converted.as_slice()).unwrap();
+    assert_eq!(writer.len(), 2 * 10 * values.len());
+
+    let mut reader = writer.as_slice();
+    let mut decoded = vec![F80::ZERO; values.len()];
+    reader.read_from_little_endian_into(decoded.as_mut_slice()).unwrap();
+    assert_eq!(decoded, converted);
+    reader.read_from_big_endian_into(decoded.as_mut_slice()).unwrap();
+    assert_eq!(decoded, converted);
+
+    assert!(F80::from(f64::NAN).is_nan());
+    assert!(F80::NAN.to_f64().is_nan());
+    assert!(F80::from_bits(0xFFFF, 1 << 63 | 1).to_f64().is_nan());
+    assert!(F80::from(-0.0_f64).is_sign_negative());
+}
+
+#[test]
+fn f80_rounds_to_nearest_f64() {
+    use lebe::f80::F80;
+
+    let one = 0x3FFF;
+    let integer_bit = 1_u64 << 63;
+
+    // ties are rounded to even
+    assert_eq!(F80::from_bits(one, integer_bit | 1).to_f64(), 1.0);
+    assert_eq!(F80::from_bits(one, integer_bit | 1 << 10).to_f64(), 1.0);
+    assert_eq!(F80::from_bits(one, integer_bit | 3 << 10).to_f64(), 1.0 + f64::EPSILON * 2.0);
+    assert_eq!(F80::from_bits(one, integer_bit | 1 << 10 | 1).to_f64(), 1.0 + f64::EPSILON);
+
+    // rounding up carries into the exponent
+    assert_eq!(F80::from_bits(one, u64::MAX).to_f64(), 2.0);
+    assert_eq!(F80::from_bits(0x43FE, u64::MAX).to_f64(), f64::INFINITY);
+    assert_eq!(F80::from_bits(0x43FE, u64::MAX >> 11 << 11).to_f64(), f64::MAX);
+
+    // out of range
+    assert_eq!(F80::from_bits(0x7FFE, integer_bit).to_f64(), f64::INFINITY);
+    assert_eq!(F80::from_bits(0xFFFE, integer_bit).to_f64(), f64::NEG_INFINITY);
+    assert_eq!(F80::from_bits(0x0001, integer_bit).to_f64(), 0.0);
+    assert_eq!(F80::from_bits(0x0000, 1).to_f64(), 0.0);
+
+    // subnormal results, including a tie that rounds up to the smallest normal number
+    let half_smallest = F80::from_bits(0x3BCC, integer_bit);
+    assert_eq!(half_smallest.to_f64(), 0.0);
+    assert_eq!(F80::from_bits(0x3BCC, integer_bit | 1).to_f64(), f64::from_bits(1));
+    assert_eq!(F80::from_bits(0x3C00, u64::MAX).to_f64(), f64::MIN_POSITIVE);
+
+    // unnormals are converted by their numeric value
+    assert_eq!(F80::from_bits(0x4000, 1 << 62).to_f64(), 1.0);
+}
+
+#[test]
+fn mixed_endian_layouts() {
+    use lebe::MixedEndian;
+
+    let mut writer = Vec::new();
+    writer.write_as_word_swapped_little_endian(&0x0A0B_0C0D_u32).unwrap();
+    writer.write_as_word_swapped_big_endian(&0x0A0B_0C0D_u32).unwrap();
+    assert_eq!(writer, [0x0B, 0x0A, 0x0D, 0x0C, 0x0C, 0x0D, 0x0A, 0x0B]);
+
+    let mut reader = writer.as_slice();
+    let pdp: u32 = reader.read_from_word_swapped_little_endian().unwrap();
+    let swapped: u32 = reader.read_from_word_swapped_big_endian().unwrap();
+    assert_eq!((pdp, swapped), (0x0A0B_0C0D, 0x0A0B_0C0D));
+
+    // ARM FPA stores the most significant word first, with little endian words
+    let mut writer = Vec::new();
+    writer.write_as_word_swapped_little_endian(&1.0_f64).unwrap();
+    assert_eq!(writer, [0x00, 0x00, 0xF0, 0x3F, 0, 0, 0, 0]);
+
+    let values: Vec<f64> = (0 .. 2500).map(|i| i as f64 * -1.5).collect();
+    let mut writer = Vec::new();
+    writer.write_as_word_swapped_little_endian(values.as_slice()).unwrap();
+    writer.write_as_word_swapped_big_endian(values.as_slice()).unwrap();
+
+    let mut reader = writer.as_slice();
+    let mut decoded = vec![0.0_f64; values.len()];
+    reader.read_from_word_swapped_little_endian_into(decoded.as_mut_slice()).unwrap();
+    assert_eq!(decoded, values);
+    reader.read_from_word_swapped_big_endian_into(decoded.as_mut_slice()).unwrap();
+    assert_eq!(decoded, values);
+
+    let mut numbers = [0x0A0B_0C0D_i32, -2];
+    numbers.convert_current_to_word_swapped_little_endian();
+    assert_eq!(numbers[0].to_ne_bytes(), [0x0B, 0x0A, 0x0D, 0x0C]);
+    numbers.convert_word_swapped_little_endian_to_current();
+    assert_eq!(numbers, [0x0A0B_0C0D, -2]);
+
+    assert_eq!(0x0A0B_0C0D_u32.from_current_into_word_swapped_big_endian().to_ne_bytes(), [0x0C, 0x0D, 0x0A, 0x0B]);
+    assert_eq!(2.5_f32.from_current_into_word_swapped_big_endian().from_word_swapped_big_endian_into_current(), 2.5);
+}
+
+#[test]
+fn packed_bcd_numbers() {
+    use lebe::bcd::{BcdLayout, NibbleOrder, SignNibble};
+
+    let mut writer = Vec::new();
+    writer.write_bcd(59_u8, 2).unwrap();
+    writer.write_bcd(1234_u32, 5).unwrap();
+    writer.write_signed_bcd(-12345_i32, 5, BcdLayout::COMP3).unwrap();
+    writer.write_signed_bcd(42_i64, 4, BcdLayout::COMP3).unwrap();
+    writer.write_bcd_with_layout(7_u16, 1, BcdLayout::COMP3).unwrap();
+
+    let swapped = BcdLayout { nibble_order: NibbleOrder::LowFirst, sign: SignNibble::Leading };
+    writer.write_signed_bcd(-7_i8, 3, swapped).unwrap();
+
+    assert_eq!(writer, [
+        0x59, 0x00, 0x12, 0x34, 0x12, 0x34, 0x5D, 0x00, 0x04, 0x2C, 0x7F, 0x0D, 0x70
+    ]);
+
+    let mut reader = writer.as_slice();
+    assert_eq!(reader.read_bcd::<u8>(2).unwrap(), 59);
+    assert_eq!(reader.read_bcd::<u32>(5).unwrap(), 1234);
+    assert_eq!(reader.read_signed_bcd::<i32>(5, BcdLayout::COMP3).unwrap(), -12345);
+    assert_eq!(reader.read_signed_bcd::<i64>(4, BcdLayout::COMP3).unwrap(), 42);
+    assert_eq!(reader.read_bcd_with_layout::<u16>(1, BcdLayout::COMP3).unwrap(), 7);
+    assert_eq!(reader.read_signed_bcd::<i8>(3, swapped).unwrap(), -7);
+    assert!(reader.is_empty());
+
+    assert_eq!(BcdLayout::COMP3.byte_len(5), 3);
+    assert!(Vec::new().write_bcd(100_u32, 2).is_err());
+    assert!(Vec::new().write_signed_bcd(-1_i32, 2, BcdLayout::UNSIGNED).is_err());
+}
+
+#[test]
+fn invalid_packed_bcd_numbers_are_rejected() {
+    use lebe::bcd::BcdLayout;
+
+    assert!({ &[0x1A_u8][..] }.read_bcd::<u8>(2).is_err());
+    assert!({ &[0x10_u8, 0x00][..] }.read_bcd::<u16>(3).is_err());
+    assert!({ &[0x12_u8, 0x37][..] }.read_signed_bcd::<i16>(3, BcdLayout::COMP3).is_err());
+    assert!({ &[0x25_u8, 0x6C][..] }.read_bcd::<u8>(3).is_err());
+    assert!({ &[0x12_u8, 0x8C][..] }.read_signed_bcd::<i8>(3, BcdLayout::COMP3).is_err());
+    assert!({ &[0x01_u8, 0x2D][..] }.read_bcd_with_layout::<u8>(3, BcdLayout::COMP3).is_err());
+    assert_eq!({ &[0x12_u8, 0x8D][..] }.read_signed_bcd::<i8>(3, BcdLayout::COMP3).unwrap(), -128);
+}
+
+#[test]
+fn truncated_reads_report_context() {
+    use std::io::ErrorKind;
+
+    let mut reader: &[u8] = &[1, 2, 3, 4, 5, 6];
+    let error = reader.read_from_little_endian_into(&mut [0_u16; 4][..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert!(error.to_string().contains("requested 8 bytes, but the stream ended"));
+
+    let error = lebe::Error::from(error);
+    assert_eq!(error.type_name(), Some("[u16]"));
+    assert_eq!(error.requested_bytes(), Some(8));
+    assert_eq!(error.available_bytes(), None);
+    assert_eq!(error.missing_bytes(), None);
+    assert_eq!(error.offset(), None);
+
+    // the context survives converting back and forth
+    let error = lebe::Error::from(std::io::Error::from(error.with_offset(12)));
+    assert_eq!((error.type_name(), error.offset()), (Some("[u16]"), Some(12)));
+    assert_eq!(error.to_string(), "failed to read `[u16]`: requested 8 bytes, but the stream ended at offset 12");
+
+    // plain errors are passed through unchanged
+    let plain = lebe::Error::from(std::io::Error::new(ErrorKind::InvalidData, "plain"));
+    assert_eq!(plain.type_name(), None);
+    assert_eq!(plain.kind(), ErrorKind::InvalidData);
+
+    let plain = std::io::Error::from(plain);
+    assert_eq!(plain.to_string(), "plain");
+    assert!(plain.get_ref().unwrap().downcast_ref::<lebe::Error>().is_none());
+
+    fn read_header(mut reader: &[u8]) -> Result<(u32, f64), lebe::Error> {
+        Ok((reader.read_from_big_endian()?, reader.read_from_big_endian()?))
+    }
+
+    let error = read_header(&[0, 0, 0, 1, 0x3F, 0xF0]).unwrap_err();
+    assert_eq!((error.type_name(), error.requested_bytes()), (Some("f64"), Some(8)));
+
+    // wrapper types are named instead of the primitive they are read as
+    let result: std::io::Result<char> = (&[0_u8, 0][..]).read_from_little_endian();
+    let error = lebe::Error::from(result.unwrap_err());
+    assert_eq!(error.type_name(), Some("char"));
+}
+
+#[test]
+fn partial_slice_reads_report_progress() {
+    let mut bytes = Vec::new();
+    bytes.write_as_big_endian(&[1.5_f32, -2.0, 3.25][..]).unwrap();
+    bytes.truncate(10);
+
+    let mut values = [0.0_f32; 4];
+    let progress = bytes.as_slice().read_partial_from_big_endian_into(&mut values[..]).into_result().unwrap();
+    assert_eq!(progress.complete_elements(), 2);
+    assert_eq!(progress.partial_bytes(), &3.25_f32.to_be_bytes()[.. 2]);
+    assert_eq!(values[.. 2], [1.5, -2.0]);
+
+    let mut values = [0_u16; 2];
+    let mut reader: &[u8] = &[1, 0, 2, 0, 3, 0];
+    let progress = reader.read_partial_from_little_endian_into(&mut values[..]).into_result().unwrap();
+    assert_eq!(progress.complete_elements(), 2);
+    assert!(progress.partial_bytes().is_empty());
+    assert_eq!(values, [1, 2]);
+    assert_eq!(reader, [3, 0]);
+
+    let mut empty: &[u8] = &[];
+    assert_eq!(empty.read_partial_from_little_endian_into(&mut values[..]).into_result().unwrap().complete_elements(), 0);
+
+    // the progress made before an error is reported together with the error
+    struct Failing;
+
+    impl std::io::Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::ConnectionReset.into())
+        }
+    }
+
+    let mut values = [0_u16; 4];
+    let progress = std::io::Read::chain(&[1_u8, 0, 2, 0, 3][..], Failing).read_partial_from_little_endian_into(&mut values[..]);
+    assert_eq!(progress.complete_elements(), 2);
+    assert_eq!(progress.partial_bytes(), [3]);
+    assert_eq!(progress.error().map(std::io::Error::kind), Some(std::io::ErrorKind::ConnectionReset));
+    assert_eq!(values[.. 2], [1, 2]);
+    assert!(progress.into_result().is_err());
+
+    let mut reader = std::io::Read::chain(&[1_u8, 0, 2, 0, 3][..], Failing);
+    let mut iter = reader.iter_little_endian::<u16>();
+    assert_eq!(iter.next().unwrap().unwrap(), 1);
+    assert_eq!(iter.next().unwrap().unwrap(), 2);
+    assert_eq!(iter.next().unwrap().unwrap_err().kind(), std::io::ErrorKind::ConnectionReset);
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn try_read_distinguishes_clean_end_from_truncation() {
+    use std::io::ErrorKind;
+
+    let mut reader: &[u8] = &[0, 0, 0, 7, 0, 0];
+    let value: Option<u32> = reader.try_read_from_big_endian().unwrap();
+    assert_eq!(value, Some(7));
+
+    let error = TryReadEndian::<u32>::try_read_from_big_endian(&mut reader).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+    let error = lebe::Error::from(error);
+    assert_eq!((error.type_name(), error.requested_bytes()), (Some("u32"), Some(4)));
+
+    let value: Option<u32> = reader.try_read_from_little_endian().unwrap();
+    assert_eq!(value, None);
+
+    let mut values = [0_u16; 2];
+    let mut reader: &[u8] = &[1, 0, 2, 0];
+    assert!(reader.try_read_from_little_endian_into(&mut values[..]).unwrap());
+    assert_eq!(values, [1, 2]);
+    assert!(!reader.try_read_from_little_endian_into(&mut values[..]).unwrap());
+    assert!(reader.try_read_from_little_endian_into(&mut values[.. 0]).unwrap());
+
+    // records are read until the stream ends cleanly
+    let mut reader: &[u8] = &[1, 0, 0, 0, 0x40, 2, 0, 0, 0, 0x41, 3, 0];
+    let mut records = Vec::new();
+
+    let error = loop {
+        let record = reader.try_read_record(|read| {
+            let id: u32 = read.read_from_little_endian()?;
+            let tag: u8 = read.read_from_little_endian()?;
+            Ok((id, tag))
+        });
+
+        match record {
+            Ok(Some(record)) => records.push(record),
+            Ok(None) => break None,
+            Err(error) => break Some(error),
+        }
+    };
+
+    assert_eq!(records, [(1, 0x40), (2, 0x41)]);
+    assert_eq!(error.unwrap().kind(), ErrorKind::UnexpectedEof);
+
+    // values are read from unbuffered streams as well
+    let mut reader = std::io::Read::take(std::io::repeat(1), 6);
+    let value: Option<u32> = reader.try_read_from_little_endian().unwrap();
+    assert_eq!(value, Some(0x0101_0101));
+    assert_eq!(TryReadEndian::<u32>::try_read_from_little_endian(&mut reader).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(TryReadEndian::<u32>::try_read_from_little_endian(&mut reader).unwrap(), None);
+}
+
+#[test]
+fn iterate_over_values_in_chunks() {
+    let values: Vec<u32> = (0 .. 2500).map(|i| i * 7919).collect();
+    let mut bytes = Vec::new();
+    bytes.write_as_little_endian(values.as_slice()).unwrap();
+
+    let decoded: Vec<u32> = bytes.as_slice().iter_little_endian::<u32>().collect::<Result<_, _>>().unwrap();
+    assert_eq!(decoded, values);
+
+    // the count variant does not read past the last value
+    let mut reader = bytes.as_slice();
+    let first: Vec<u32> = reader.iter_little_endian_count::<u32>(1500).collect::<Result<_, _>>().unwrap();
+    assert_eq!(first, values[.. 1500]);
+    assert_eq!(reader.len(), 4 * 1000);
+
+    let iter = reader.iter_little_endian_count::<u32>(5000);
+    assert_eq!(iter.size_hint(), (0, Some(5000)));
+    assert_eq!(iter.count(), 1000);
+
+    // a truncated last value results in an error after all complete values
+    let mut reader: &[u8] = &[0, 1, 0, 2, 0];
+    let mut iter = reader.iter_big_endian::<u16>();
+    assert_eq!(iter.next().unwrap().unwrap(), 1);
+    assert_eq!(iter.next().unwrap().unwrap(), 2);
+    assert_eq!(iter.next().unwrap().unwrap_err().kind(), std::io::ErrorKind::UnexpectedEof);
+    assert!(iter.next().is_none());
+
+    let mut empty: &[u8] = &[];
+    assert!(empty.iter_big_endian::<f64>().next().is_none());
+}
+
+#[test]
+fn write_values_from_iterators() {
+    let mut writer = Vec::new();
+    let count = writer.write_iter_as_big_endian((0 .. 3000_u32).map(|i| i * 3)).unwrap();
+    assert_eq!(count, 3000);
+    assert_eq!(writer.len(), 4 * 3000);
+
+    let mut expected = Vec::new();
+    expected.write_as_big_endian((0 .. 3000_u32).map(|i| i * 3).collect::<Vec<_>>().as_slice()).unwrap();
+    assert_eq!(writer, expected);
+
+    // values are written in chunks in both byte orders, instead of one by one
+    struct CountWrites(usize);
+
+    impl std::io::Write for CountWrites {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> { self.0 += 1; Ok(bytes.len()) }
+        fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+    }
+
+    let mut counter = CountWrites(0);
+    counter.write_iter_as_big_endian(0 .. 3000_u32).unwrap();
+    counter.write_iter_as_little_endian(0 .. 3000_u32).unwrap();
+    assert_eq!(counter.0, 6);
+
+    let mut writer = Vec::new();
+    assert_eq!(writer.write_iter_as_little_endian([1.5_f64, -2.0].iter().copied()).unwrap(), 2);
+    assert_eq!(writer.write_iter_as_little_endian(std::iter::empty::<i16>()).unwrap(), 0);
+    assert_eq!(writer[.. 8], 1.5_f64.to_le_bytes());
+    assert_eq!(writer.len(), 16);
+}
+
+#[test]
+fn read_directly_from_buffered_readers() {
+    use std::io::BufReader;
+
+    let mut bytes = Vec::new();
+    for i in 0 .. 100_u32 { bytes.write_as_big_endian(&(i * 1000)).unwrap(); }
+    bytes.write_as_big_endian(&true).unwrap();
+
+    // a capacity that is not a multiple of four makes values cross the end of the buffer
+    let mut reader = BufReader::with_capacity(10, bytes.as_slice());
+    assert_eq!(reader.peek_from_big_endian::<u16>().unwrap(), Some(0));
+
+    for i in 0 .. 100_u32 {
+        let value: u32 = reader.read_buffered_from_big_endian().unwrap();
+        assert_eq!(value, i * 1000);
+    }
+
+    assert_eq!(reader.peek_from_big_endian::<bool>().unwrap(), Some(true));
+    assert!(reader.read_buffered_from_big_endian::<bool>().unwrap());
+    assert_eq!(reader.peek_from_big_endian::<u8>().unwrap(), None);
+
+    let mut reader = BufReader::new(&[0x7F_u8, 0, 0, 1, 0, 0, 0, 2, 0][..]);
+    assert_eq!(reader.peek_from_little_endian::<u32>().unwrap(), Some(0x0100_007F));
+    assert_eq!(reader.peek_from_big_endian::<u32>().unwrap(), Some(0x7F00_0001));
+
+    let mut values = [0_u32; 2];
+    reader.read_buffered_from_little_endian_into(&mut values[..]).unwrap();
+    assert_eq!(values, [0x0100_007F, 0x0200_0000]);
+    assert!(reader.read_buffered_from_little_endian::<u16>().is_err());
+
+    // a value crossing the end of the buffer is not available for peeking, but can still be read
+    let mut reader = BufReader::with_capacity(3, &[0_u8, 0, 1, 0][..]);
+    assert_eq!(reader.read_buffered_from_big_endian::<u8>().unwrap(), 0);
+    assert_eq!(reader.peek_from_big_endian::<u32>().unwrap(), None);
+    assert_eq!(reader.read_buffered_from_big_endian::<u16>().unwrap(), 1);
+    assert_eq!(reader.peek_from_big_endian::<u8>().unwrap(), Some(0));
+
+    // types that take up fewer bytes in the stream than in memory are decoded from the buffer as well
+    use lebe::f80::F80;
+    use lebe::int24::U24;
+
+    let mut reader = BufReader::with_capacity(3, &[0x01_u8, 0x02, 0x03, 0x04][..]);
+    assert_eq!(reader.peek_from_big_endian::<U24>().unwrap(), Some(U24::from_be_bytes([1, 2, 3])));
+    assert_eq!(reader.read_buffered_from_big_endian::<U24>().unwrap().get(), 0x01_0203);
+    assert_eq!(reader.read_buffered_from_big_endian::<u8>().unwrap(), 4);
+
+    // the sample rate of an aiff file, which fills the whole buffer
+    let mut reader = BufReader::with_capacity(10, &[0x40_u8, 0x0E, 0xAC, 0x44, 0, 0, 0, 0, 0, 0][..]);
+    assert_eq!(reader.peek_from_big_endian::<F80>().unwrap().map(F80::to_f64), Some(44100.0));
+    assert_eq!(reader.read_buffered_from_big_endian::<F80>().unwrap().to_f64(), 44100.0);
+    assert_eq!(reader.peek_from_big_endian::<u8>().unwrap(), None);
+}
+
+#[test]
+fn fill_in_placeholders_later() {
+    use lebe::ByteOrder;
+    use lebe::positioned::PositionedWriter;
+    use std::io::{ Cursor, ErrorKind, Write };
+
+    // a riff chunk, whose size is only known after writing its contents
+    let mut writer = PositionedWriter::new(Vec::new());
+    writer.write_all(b"RIFF").unwrap();
+    let size = writer.reserve::<u32>(ByteOrder::LittleEndian).unwrap();
+    let offset = writer.reserve::<u64>(ByteOrder::BigEndian).unwrap();
+    writer.write_as_little_endian(&[7_u16, 8, 9][..]).unwrap();
+
+    assert_eq!(writer.position(), 4 + 4 + 8 + 6);
+    assert!(writer.get_ref().len() == 4);
+
+    writer.fill(offset, &0xABCD).unwrap();
+    assert_eq!(writer.fill(offset, &0).unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert_eq!(writer.unfilled_placeholders(), 1);
+
+    writer.fill(size, &(writer.position() as u32 - 8)).unwrap();
+    let expected = [b'R',b'I',b'F',b'F', 14,0,0,0, 0,0,0,0,0,0,0xAB,0xCD, 7,0, 8,0, 9,0];
+    assert_eq!(writer.finish().unwrap(), expected);
+
+    // seeking writes the placeholder contents directly
+    let mut cursor = Cursor::new(vec![0xFF_u8; 2]);
+    cursor.set_position(2);
+
+    let mut writer = PositionedWriter::seekable(cursor).unwrap();
+    let count = writer.reserve::<u16>(ByteOrder::BigEndian).unwrap();
+    writer.write_as_big_endian(&1.5_f32).unwrap();
+    assert_eq!(writer.get_ref().get_ref().len(), 2 + 2 + 4);
+
+    writer.fill(count, &1).unwrap();
+    writer.write_as_big_endian(&true).unwrap();
+    assert_eq!(writer.finish().unwrap().into_inner(), [0xFF, 0xFF, 0, 1, 0x3F, 0xC0, 0, 0, 1]);
+
+    // buffered bytes that could not be written after filling are written before anything else
+    struct FailsOnce { bytes: Vec<u8>, failed: bool }
+
+    impl Write for FailsOnce {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            if !self.failed && !self.bytes.is_empty() {
+                self.failed = true;
+                return Err(ErrorKind::ConnectionReset.into());
+            }
+
+            self.bytes.write(bytes)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+    }
+
+    let mut writer = PositionedWriter::new(FailsOnce { bytes: Vec::new(), failed: false });
+    writer.write_all(&[1]).unwrap();
+    let count = writer.reserve::<u16>(ByteOrder::BigEndian).unwrap();
+    writer.write_all(&[2, 3]).unwrap();
+
+    assert_eq!(writer.fill(count, &0x0405).unwrap_err().kind(), ErrorKind::ConnectionReset);
+    writer.write_all(&[6]).unwrap();
+    assert_eq!(writer.finish().unwrap().bytes, [1, 4, 5, 2, 3, 6]);
+
+    // placeholders of another writer are rejected, even if this writer has one at the same index
+    let mut first = PositionedWriter::new(Vec::new());
+    let foreign = first.reserve::<u32>(ByteOrder::LittleEndian).unwrap();
+
+    let mut second = PositionedWriter::new(Vec::new());
+    second.write_all(&[1, 2, 3, 4]).unwrap();
+    let own = second.reserve::<u32>(ByteOrder::LittleEndian).unwrap();
+    assert_eq!(second.fill(foreign, &5).unwrap_err().kind(), ErrorKind::InvalidInput);
+    second.fill(own, &5).unwrap();
+    first.fill(foreign, &6).unwrap();
+    assert_eq!(second.finish().unwrap(), [1, 2, 3, 4, 5, 0, 0, 0]);
+
+    let mut writer = PositionedWriter::new(Vec::new());
+    writer.reserve::<i32>(ByteOrder::LittleEndian).unwrap();
+    assert_eq!(writer.finish().unwrap_err().kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn read_nested_chunks_without_reading_past_their_end() {
+    use std::io::{ BufReader, ErrorKind };
+
+    // an iff form containing two chunks, followed by a trailer
+    let bytes: &[u8] = &[
+        b'F',b'O',b'R',b'M', 0,0,0,20,
+        b'A',b'B',b'C',b'D', 0,0,0,2, 0x12,0x34,
+        b'E',b'F',b'G',b'H', 0,0,0,2, 0x56,0x78,
+        0xFF,
+    ];
+
+    let mut reader = bytes;
+    assert_eq!(u32::read_from_big_endian(&mut reader).unwrap(), u32::from_be_bytes(*b"FORM"));
+    let len: u32 = reader.read_from_big_endian().unwrap();
+
+    {
+        let mut form = reader.chunk(u64::from(len));
+        let mut first = [0_u8; 4];
+        form.read_from_big_endian_into(&mut first[..]).unwrap();
+        assert_eq!(&first, b"ABCD");
+
+        let len: u32 = form.read_from_big_endian().unwrap();
+        let mut chunk = form.chunk(u64::from(len));
+        assert_eq!(u16::read_from_big_endian(&mut chunk).unwrap(), 0x1234);
+        assert_eq!(chunk.remaining(), 0);
+
+        let error = u8::read_from_big_endian(&mut chunk).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+        chunk.finish().unwrap();
+
+        // only read the identifier of the second chunk, and let dropping skip the rest
+        assert_eq!(form.remaining(), 10);
+        form.read_from_big_endian_into(&mut first[..]).unwrap();
+        assert_eq!(&first, b"EFGH");
+    }
+
+    assert_eq!(u8::read_from_big_endian(&mut reader).unwrap(), 0xFF);
+
+    // buffered readers stay within the chunk as well
+    let mut reader = BufReader::new(&[1_u8, 2, 3, 4, 5][..]);
+    let mut chunk = reader.chunk(3);
+    assert_eq!(chunk.read_buffered_from_little_endian::<u16>().unwrap(), 0x0201);
+    assert_eq!(chunk.peek_from_little_endian::<u16>().unwrap(), None);
+    chunk.finish().unwrap();
+    assert_eq!(u16::read_from_little_endian(&mut reader).unwrap(), 0x0504);
+
+    // a stream that ends inside the chunk
+    let mut reader: &[u8] = &[1, 2];
+    assert_eq!(reader.chunk(4).finish().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+
+    // a stream that fails once while skipping, after which neither dropping nor finishing skips again
+    struct FailsOnce<'a> { bytes: &'a [u8], failed: bool }
+
+    impl std::io::Read for FailsOnce<'_> {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            if !self.failed && self.bytes.len() == 6 {
+                self.failed = true;
+                return Err(ErrorKind::ConnectionReset.into());
+            }
+
+            let len = buffer.len().min(1);
+            self.bytes.read(&mut buffer[.. len])
+        }
+    }
+
+    let mut reader = FailsOnce { bytes: &[1, 2, 3, 4, 5, 6, 7, 8], failed: false };
+    assert_eq!(reader.chunk(4).finish().unwrap_err().kind(), ErrorKind::ConnectionReset);
+    assert_eq!(u8::read_from_big_endian(&mut reader).unwrap(), 3);
+}
+
+#[test]
+fn pad_and_skip_to_aligned_positions() {
+    use lebe::positioned::{ PositionedWriter, PositionedReader };
+    use std::io::{ Cursor, ErrorKind };
+
+    let mut writer = PositionedWriter::new(Vec::new());
+    writer.write_as_little_endian(&[1_u8, 2, 3][..]).unwrap();
+    writer.write_padding_to_alignment(4).unwrap();
+    writer.write_padding_to_alignment(4).unwrap();
+    writer.write_as_little_endian(&7_u16).unwrap();
+    writer.write_padding_to_alignment(8).unwrap();
+    writer.write_zeros(2).unwrap();
+    assert_eq!(writer.write_padding_to_alignment(0).unwrap_err().kind(), ErrorKind::InvalidInput);
+
+    let bytes = writer.finish().unwrap();
+    assert_eq!(bytes, [1, 2, 3, 0, 7, 0, 0, 0, 0, 0]);
+
+    // skip by reading and discarding
+    let mut reader = PositionedReader::new(bytes.as_slice());
+    reader.skip_bytes(1).unwrap();
+    reader.align_reader_to(4).unwrap();
+    assert_eq!(u16::read_from_little_endian(&mut reader).unwrap(), 7);
+    reader.align_reader_to(8).unwrap();
+    assert_eq!(reader.position(), 8);
+    assert_eq!(reader.skip_bytes(3).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+
+    // skip by seeking
+    let mut cursor = Cursor::new(bytes);
+    cursor.set_position(1);
+
+    let mut reader = PositionedReader::seekable(cursor).unwrap();
+    reader.align_reader_to(2).unwrap();
+    reader.skip_bytes(2).unwrap();
+    reader.align_reader_to(4).unwrap();
+    assert_eq!(reader.get_ref().position(), 1 + 4);
+    reader.skip_bytes(1).unwrap();
+    assert_eq!(u8::read_from_little_endian(&mut reader).unwrap(), 0);
+
+    // seeking past the end stops at the end, and the position stays correct
+    assert_eq!(reader.skip_bytes(5).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!((reader.position(), reader.get_ref().position()), (9, 10));
+
+    // aligned positions and empty skips do not seek at all
+    struct CountSeeks { inner: Cursor<Vec<u8>>, seeks: usize }
+
+    impl std::io::Read for CountSeeks {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> { self.inner.read(buffer) }
+    }
+
+    impl std::io::Seek for CountSeeks {
+        fn seek(&mut self, position: std::io::SeekFrom) -> std::io::Result<u64> {
+            self.seeks += 1;
+            self.inner.seek(position)
+        }
+    }
+
+    let mut reader = PositionedReader::seekable(CountSeeks { inner: Cursor::new(vec![0; 8]), seeks: 0 }).unwrap();
+    let seeks = reader.get_ref().seeks;
+    reader.align_reader_to(4).unwrap();
+    reader.skip_bytes(0).unwrap();
+    reader.skip_bytes(4).unwrap();
+    reader.align_reader_to(4).unwrap();
+    assert_eq!(reader.get_ref().seeks, seeks + 1);
+
+    // streams that can seek can also skip without a positioned reader
+    let mut cursor = Cursor::new([1_u8, 2, 3, 4]);
+    cursor.skip_bytes_by_seeking(3).unwrap();
+    assert_eq!(cursor.position(), 3);
+    assert_eq!(cursor.skip_bytes_by_seeking(2).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(cursor.position(), 4);
+
+    // the extension traits work on any stream
+    let mut writer = Vec::new();
+    writer.write_zeros(2000).unwrap();
+    assert_eq!(writer.len(), 2000);
+
+    let mut reader: &[u8] = &[1, 2, 3];
+    reader.skip_bytes(2).unwrap();
+    assert_eq!(reader, [3]);
+}